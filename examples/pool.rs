@@ -0,0 +1,93 @@
+//! A comparison of sending messages with and without a `MessagePool`.
+//!
+//! Both loops push the same messages through an in-process PUSH/PULL pair. The
+//! first allocates a new message for every iteration and frees every received
+//! message, while the second takes messages from a pool and returns the
+//! received ones to it, so that the same few `nng_msg` objects are reused.
+//!
+//! Run this with `cargo run --release --example pool [ITERATIONS]`.
+use std::{
+    env,
+    time::{Duration, Instant},
+};
+
+use nng::{Message, MessagePool, Protocol, Socket};
+
+/// Size of the body of each message.
+const MSG_SIZE: usize = 1024;
+
+/// Number of iterations used when none are given on the command line.
+const DEFAULT_ITERATIONS: usize = 100_000;
+
+/// Entry point of the application
+fn main() -> Result<(), nng::Error> {
+    let iterations = env::args()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_ITERATIONS);
+
+    let (push, pull) = pair("inproc://nng/examples/pool")?;
+    let payload = vec![0xA5; MSG_SIZE];
+
+    let unpooled = time(|| {
+        for _ in 0..iterations {
+            let mut msg = Message::with_capacity(MSG_SIZE);
+            msg.push_back(&payload);
+            push.send(msg).map_err(|(_, e)| e)?;
+
+            // Dropping the received message frees it.
+            let _ = pull.recv()?;
+        }
+        Ok(())
+    })?;
+
+    let pool = MessagePool::new(16);
+    let pooled = time(|| {
+        for _ in 0..iterations {
+            let mut msg = pool.get();
+            msg.push_back(&payload);
+            push.send(msg).map_err(|(_, e)| e)?;
+
+            // Handing the received message back lets the next `get` reuse it.
+            pool.put(pull.recv()?);
+        }
+        Ok(())
+    })?;
+
+    println!("{} messages of {} bytes", iterations, MSG_SIZE);
+    report("without pool", unpooled, iterations);
+    report("with pool", pooled, iterations);
+    println!("idle messages left in the pool: {}", pool.len());
+
+    Ok(())
+}
+
+/// Creates a connected PUSH/PULL pair on the given address.
+fn pair(url: &str) -> Result<(Socket, Socket), nng::Error> {
+    let pull = Socket::new(Protocol::Pull0)?;
+    pull.listen(url)?;
+
+    let push = Socket::new(Protocol::Push0)?;
+    push.dial(url)?;
+
+    Ok((push, pull))
+}
+
+/// Runs the closure and returns how long it took.
+fn time<F>(f: F) -> Result<Duration, nng::Error>
+where
+    F: FnOnce() -> Result<(), nng::Error>,
+{
+    let start = Instant::now();
+    f()?;
+    Ok(start.elapsed())
+}
+
+/// Prints the total and per-message time of a run.
+fn report(name: &str, elapsed: Duration, iterations: usize) {
+    let per_msg = elapsed.as_nanos() / iterations.max(1) as u128;
+    println!(
+        "{:>14}: {:?} total, {} ns per message",
+        name, elapsed, per_msg
+    );
+}
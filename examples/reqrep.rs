@@ -66,7 +66,7 @@ fn server(url: &str) -> Result<(), nng::Error> {
             .as_secs();
 
         msg.clear();
-        msg.push_back(&rep.to_le_bytes());
+        msg.push_u64_le(rep);
 
         println!("SERVER: SENDING {}", rep);
         s.send(msg)?;
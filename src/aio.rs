@@ -4,8 +4,9 @@ use std::{
 	os::raw::c_void,
 	ptr::{self, NonNull},
 	sync::{
-		atomic::{AtomicPtr, AtomicUsize, Ordering},
-		Arc,
+		atomic::{AtomicI32, AtomicPtr, AtomicUsize, Ordering},
+		mpsc::{self, Receiver},
+		Arc, Mutex,
 	},
 	time::Duration,
 };
@@ -15,7 +16,7 @@ use crate::{
 	error::{Error, Result, SendResult},
 	message::Message,
 	socket::Socket,
-	util::{abort_unwind, duration_to_nng, validate_ptr},
+	util::{abort_unwind, duration_to_nng, nng_to_duration, validate_ptr},
 };
 use log::error;
 
@@ -154,7 +155,9 @@ impl Aio
 		let inner = Arc::new(Inner {
 			handle:   AtomicPtr::new(ptr::null_mut()),
 			state:    AtomicUsize::new(State::Inactive as usize),
+			start:    Mutex::new(()),
 			callback: AtomicPtr::new(ptr::null_mut()),
+			timeout:  AtomicI32::new(nng_sys::NNG_DURATION_INFINITE),
 		});
 
 		// Now, we create the weak reference to the inner bits that will be stored
@@ -236,6 +239,53 @@ impl Aio
 		Ok(Self { inner })
 	}
 
+	/// Creates a new asynchronous I/O handle that reports results over a
+	/// channel.
+	///
+	/// Instead of calling a user-provided callback, the result of every
+	/// operation is sent to the returned `Receiver`. This allows a thread to
+	/// consume the results of operations in a loop. The channel is closed
+	/// once all handles to the `Aio` have been dropped.
+	///
+	/// # Errors
+	///
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use std::time::Duration;
+	/// use nng::{Aio, AioResult};
+	///
+	/// let (aio, results) = Aio::with_channel().unwrap();
+	/// aio.sleep(Duration::from_millis(10)).unwrap();
+	///
+	/// match results.recv().unwrap() {
+	///     AioResult::Sleep(Ok(_)) => {},
+	///     _ => panic!("unexpected result"),
+	/// }
+	///
+	/// drop(aio);
+	/// assert!(results.recv().is_err());
+	/// ```
+	///
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	pub fn with_channel() -> Result<(Self, Receiver<AioResult>)>
+	{
+		// The sender is not `Sync`, so we need to put it behind a lock in order
+		// to use it from the callback.
+		let (tx, rx) = mpsc::channel();
+		let tx = Mutex::new(tx);
+
+		// If the receiver has been dropped then nobody cares about the result, so
+		// it is fine to throw it away.
+		let aio = Aio::new(move |_, res| {
+			let _ = tx.lock().unwrap().send(res);
+		})?;
+
+		Ok((aio, rx))
+	}
+
 	/// Set the timeout of asynchronous operations.
 	///
 	/// This causes a timer to be started when the operation is actually
@@ -255,6 +305,7 @@ impl Aio
 	/// [`TimedOut`]: enum.Error.html#variant.TimedOut
 	pub fn set_timeout(&self, dur: Option<Duration>) -> Result<()>
 	{
+		let _start = self.inner.start.lock().unwrap();
 		// We need to check that no operations are happening and then prevent them from
 		// happening while we set the timeout. Any state that isn't `Inactive` will do
 		// so the choice is arbitrary. That being said, `Sleeping` feels the most
@@ -269,6 +320,7 @@ impl Aio
 			unsafe {
 				nng_sys::nng_aio_set_timeout(aiop, ms);
 			}
+			self.inner.timeout.store(ms, Ordering::Relaxed);
 
 			self.inner.state.store(inactive, Ordering::Release);
 			Ok(())
@@ -278,6 +330,17 @@ impl Aio
 		}
 	}
 
+	/// Returns the timeout of asynchronous operations.
+	///
+	/// This is the value most recently provided to [`set_timeout`]. A value
+	/// of `None` means that operations never time out, which is the default.
+	///
+	/// [`set_timeout`]: #method.set_timeout
+	pub fn timeout(&self) -> Option<Duration>
+	{
+		nng_to_duration(self.inner.timeout.load(Ordering::Relaxed))
+	}
+
 	/// Begins a sleep operation on the `Aio` and returns immediately.
 	///
 	/// If the sleep finishes completely, it will never return an error. If a
@@ -293,6 +356,7 @@ impl Aio
 	/// [`TimedOut`]: enum.Error.html#variant.TimedOut
 	pub fn sleep(&self, dur: Duration) -> Result<()>
 	{
+		let _start = self.inner.start.lock().unwrap();
 		let sleeping = State::Sleeping as usize;
 		let inactive = State::Inactive as usize;
 		let old_state = self.inner.state.compare_and_swap(inactive, sleeping, Ordering::AcqRel);
@@ -332,9 +396,48 @@ impl Aio
 		}
 	}
 
+	/// Cancel the currently running I/O operation, if there is one.
+	///
+	/// Returns `true` if an operation was running when this function was
+	/// called. No new operation can be started on the `Aio` between the check
+	/// and the cancellation, so an operation started afterwards (for example,
+	/// from the completion callback) is never canceled by mistake. The running
+	/// operation may still complete normally before the cancellation takes
+	/// effect, in which case the callback will receive the actual result of the
+	/// operation rather than [`Canceled`].
+	///
+	/// [`Canceled`]: enum.Error.html#variant.Canceled
+	pub fn try_cancel(&self) -> bool
+	{
+		let _start = self.inner.start.lock().unwrap();
+		if !self.busy() {
+			return false;
+		}
+
+		self.cancel();
+		true
+	}
+
+	/// Returns true if an operation is currently running on the `Aio`.
+	///
+	/// The state is marked as idle just before the completion callback is
+	/// called, so this returns `false` from within the callback and new
+	/// operations can be started from there. Since other threads may start or
+	/// complete operations at any time, the result is only a snapshot; starting
+	/// an operation can still fail with [`IncorrectState`] if another thread
+	/// starts one first.
+	///
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
+	pub fn busy(&self) -> bool
+	{
+		let state: State = self.inner.state.load(Ordering::Acquire).into();
+		state != State::Inactive
+	}
+
 	/// Send a message on the provided socket.
 	pub(crate) fn send_socket(&self, socket: &Socket, msg: Message) -> SendResult<()>
 	{
+		let _start = self.inner.start.lock().unwrap();
 		let inactive = State::Inactive as usize;
 		let sending = State::Sending as usize;
 
@@ -357,6 +460,7 @@ impl Aio
 	/// Receive a message on the provided socket.
 	pub(crate) fn recv_socket(&self, socket: &Socket) -> Result<()>
 	{
+		let _start = self.inner.start.lock().unwrap();
 		let inactive = State::Inactive as usize;
 		let receiving = State::Receiving as usize;
 		let old_state = self.inner.state.compare_and_swap(inactive, receiving, Ordering::AcqRel);
@@ -376,6 +480,7 @@ impl Aio
 	/// Send a message on the provided context.
 	pub(crate) fn send_ctx(&self, ctx: &Context, msg: Message) -> SendResult<()>
 	{
+		let _start = self.inner.start.lock().unwrap();
 		let inactive = State::Inactive as usize;
 		let sending = State::Sending as usize;
 
@@ -398,6 +503,7 @@ impl Aio
 	/// Receive a message on the provided context.
 	pub(crate) fn recv_ctx(&self, ctx: &Context) -> Result<()>
 	{
+		let _start = self.inner.start.lock().unwrap();
 		let inactive = State::Inactive as usize;
 		let receiving = State::Receiving as usize;
 		let old_state = self.inner.state.compare_and_swap(inactive, receiving, Ordering::AcqRel);
@@ -516,10 +622,22 @@ struct Inner
 	/// The current state of the AIO object, represented as a `usize`.
 	state: AtomicUsize,
 
+	/// Held while an operation is being started or checked for cancellation.
+	///
+	/// This keeps a new operation from starting between `try_cancel` seeing
+	/// that one is running and asking NNG to cancel it.
+	start: Mutex<()>,
+
 	/// The callback function.
 	///
 	/// We're OK with the extra layer of indirection because we never call it.
 	callback: AtomicPtr<InnerCallback>,
+
+	/// The timeout most recently set on the AIO object.
+	///
+	/// NNG does not provide a way to read the timeout back, so we keep track of
+	/// it ourselves.
+	timeout: AtomicI32,
 }
 
 impl Drop for Inner
@@ -552,6 +670,49 @@ impl Drop for Inner
 	}
 }
 
+/// A bare `nng_aio` with no callback.
+///
+/// This is used internally for operations that are performed synchronously by
+/// starting them and then immediately waiting for the result.
+pub(crate) struct RawAio
+{
+	/// The underlying NNG AIO object.
+	aio: NonNull<nng_sys::nng_aio>,
+}
+impl RawAio
+{
+	/// Allocates a new AIO object.
+	pub(crate) fn new() -> Result<Self>
+	{
+		let mut aio: *mut nng_sys::nng_aio = ptr::null_mut();
+		let rv = unsafe { nng_sys::nng_aio_alloc(&mut aio as _, None, ptr::null_mut()) };
+
+		validate_ptr(rv, aio).map(|aio| RawAio { aio })
+	}
+
+	/// Returns the underlying `nng_aio`.
+	pub(crate) const fn as_ptr(&self) -> *mut nng_sys::nng_aio { self.aio.as_ptr() }
+
+	/// Waits for the running operation to complete and returns its result.
+	pub(crate) fn wait(&self) -> Result<()>
+	{
+		let rv = unsafe {
+			nng_sys::nng_aio_wait(self.aio.as_ptr());
+			nng_sys::nng_aio_result(self.aio.as_ptr())
+		};
+
+		rv2res!(rv)
+	}
+}
+
+impl Drop for RawAio
+{
+	fn drop(&mut self)
+	{
+		unsafe { nng_sys::nng_aio_free(self.aio.as_ptr()) }
+	}
+}
+
 /// The result of an [`Aio`] operation.
 ///
 ///
@@ -574,6 +735,80 @@ pub enum AioResult
 	Sleep(Result<()>),
 }
 
+impl AioResult
+{
+	/// Returns true if the operation completed successfully.
+	pub fn is_ok(&self) -> bool
+	{
+		match self {
+			AioResult::Send(r) => r.is_ok(),
+			AioResult::Recv(r) => r.is_ok(),
+			AioResult::Sleep(r) => r.is_ok(),
+		}
+	}
+
+	/// Returns true if the operation failed.
+	pub fn is_err(&self) -> bool { !self.is_ok() }
+
+	/// Returns the error of the operation, if it failed.
+	pub fn err(&self) -> Option<Error>
+	{
+		match self {
+			AioResult::Send(Err((_, e))) | AioResult::Recv(Err(e)) | AioResult::Sleep(Err(e)) => {
+				Some(*e)
+			},
+			_ => None,
+		}
+	}
+
+	/// Returns the message held by the result, if any.
+	///
+	/// This is the received message for a successful receive operation and the
+	/// unsent message for a failed send operation. All other results do not
+	/// hold a message.
+	pub fn into_message(self) -> Option<Message>
+	{
+		match self {
+			AioResult::Recv(Ok(m)) | AioResult::Send(Err((m, _))) => Some(m),
+			_ => None,
+		}
+	}
+
+	/// Separates transient failures from the final result of the operation.
+	///
+	/// If the operation failed with [`TryAgain`] or [`TimedOut`], in which case
+	/// it is usually appropriate to start it again, the result itself is
+	/// returned as the error. For a send operation, [`into_message`] then
+	/// recovers the unsent message so that it can be sent again. Otherwise,
+	/// the result is converted in the same manner as the `From` implementation
+	/// for `Result<Option<Message>>`.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{AioResult, Error, Message};
+	///
+	/// let msg = Message::from(&b"hello"[..]);
+	/// let retry = AioResult::Send(Err((msg, Error::TimedOut)));
+	/// let unsent = retry.ok_or_retry().unwrap_err().into_message().unwrap();
+	/// assert_eq!(&unsent[..], b"hello");
+	///
+	/// let fatal = AioResult::Sleep(Err(Error::Closed));
+	/// assert_eq!(fatal.ok_or_retry().unwrap().unwrap_err(), Error::Closed);
+	/// ```
+	///
+	/// [`into_message`]: #method.into_message
+	/// [`TimedOut`]: enum.Error.html#variant.TimedOut
+	/// [`TryAgain`]: enum.Error.html#variant.TryAgain
+	pub fn ok_or_retry(self) -> std::result::Result<Result<Option<Message>>, AioResult>
+	{
+		match self.err() {
+			Some(Error::TryAgain) | Some(Error::TimedOut) => Err(self),
+			_ => Ok(self.into()),
+		}
+	}
+}
+
 impl From<AioResult> for Result<Option<Message>>
 {
 	fn from(aio_res: AioResult) -> Result<Option<Message>>
@@ -0,0 +1,101 @@
+use std::{error, fmt};
+
+use serde_crate::{de::DeserializeOwned, Serialize};
+
+use crate::{error::Error, message::Message};
+
+/// A format for converting values to and from message bodies.
+///
+/// The [`Socket::send_value_with`] and [`Socket::recv_value_with`] functions
+/// accept any codec, while [`Socket::send_value`] and [`Socket::recv_value`]
+/// use the [`Bincode`] codec by default. Other formats can be used by
+/// implementing this trait.
+///
+/// ## Example
+///
+/// ```
+/// use nng::{Bincode, Codec};
+///
+/// let msg = Bincode::encode(&(1u32, String::from("hello"))).unwrap();
+/// let value: (u32, String) = Bincode::decode(&msg).unwrap();
+/// assert_eq!(value, (1, String::from("hello")));
+/// ```
+///
+/// [`Bincode`]: struct.Bincode.html
+/// [`Socket::recv_value`]: struct.Socket.html#method.recv_value
+/// [`Socket::recv_value_with`]: struct.Socket.html#method.recv_value_with
+/// [`Socket::send_value`]: struct.Socket.html#method.send_value
+/// [`Socket::send_value_with`]: struct.Socket.html#method.send_value_with
+pub trait Codec
+{
+	/// Serializes the value into a new message.
+	#[allow(clippy::missing_errors_doc)]
+	fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Message, CodecError>;
+
+	/// Deserializes a value from the body of the message.
+	#[allow(clippy::missing_errors_doc)]
+	fn decode<T: DeserializeOwned>(msg: &Message) -> Result<T, CodecError>;
+}
+
+/// A codec using the [bincode][1] format.
+///
+/// [1]: https://docs.rs/bincode
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Bincode;
+
+impl Codec for Bincode
+{
+	fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Message, CodecError>
+	{
+		let mut msg = Message::new();
+		bincode::serialize_into(&mut msg, value).map_err(bincode_err)?;
+
+		Ok(msg)
+	}
+
+	fn decode<T: DeserializeOwned>(msg: &Message) -> Result<T, CodecError>
+	{
+		bincode::deserialize(msg.as_slice()).map_err(bincode_err)
+	}
+}
+
+/// Converts a bincode error into a `CodecError`.
+fn bincode_err(e: bincode::Error) -> CodecError { CodecError::Codec(e) }
+
+/// An error that occurred while sending or receiving a serialized value.
+#[derive(Debug)]
+pub enum CodecError
+{
+	/// The underlying NNG operation failed.
+	Nng(Error),
+
+	/// The value could not be serialized or deserialized.
+	Codec(Box<dyn error::Error + Send + Sync + 'static>),
+}
+
+impl From<Error> for CodecError
+{
+	fn from(e: Error) -> CodecError { CodecError::Nng(e) }
+}
+
+impl fmt::Display for CodecError
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		match self {
+			CodecError::Nng(e) => write!(f, "{}", e),
+			CodecError::Codec(e) => write!(f, "Codec error: {}", e),
+		}
+	}
+}
+
+impl error::Error for CodecError
+{
+	fn source(&self) -> Option<&(dyn error::Error + 'static)>
+	{
+		match self {
+			CodecError::Nng(e) => Some(e),
+			CodecError::Codec(e) => Some(&**e),
+		}
+	}
+}
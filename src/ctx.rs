@@ -5,8 +5,9 @@ use std::{
 };
 
 use crate::{
-	aio::Aio,
+	aio::{Aio, AioResult},
 	error::{Result, SendResult},
+	future::{RecvFuture, SendFuture, Source},
 	message::Message,
 	socket::Socket,
 };
@@ -20,6 +21,33 @@ use crate::{
 ///
 /// Note that not all protocols allow for the creation of contexts.
 ///
+/// ## Raw Sockets
+///
+/// Contexts cannot be created on a [`RawSocket`]. With a raw socket, the
+/// protocol state normally tracked by each context is carried in the message
+/// header instead, so a scalable raw _rep_ server can receive requests with
+/// several [`Aio`] objects in parallel. Each reply must carry the header of
+/// the request it answers, which is most easily done using
+/// [`Message::copy_header_from`]. Without the header, the reply cannot be
+/// routed back to the correct requester and is silently dropped.
+///
+/// ```
+/// use nng::{Message, Protocol, RawSocket};
+///
+/// let server = RawSocket::new(Protocol::Rep0).unwrap();
+/// server.socket.listen("inproc://nng/ctx/raw").unwrap();
+///
+/// # let client = nng::Socket::new(Protocol::Req0).unwrap();
+/// # client.dial("inproc://nng/ctx/raw").unwrap();
+/// # client.send(&b"ping"[..]).unwrap();
+/// let request = server.socket.recv().unwrap();
+///
+/// let mut reply = Message::from(&b"pong"[..]);
+/// reply.copy_header_from(&request);
+/// server.socket.send(reply).unwrap();
+/// # assert_eq!(&client.recv().unwrap()[..], b"pong");
+/// ```
+///
 /// ## Examples
 ///
 /// See the documentation of the [`Aio`] type for examples on how to use socket
@@ -27,6 +55,8 @@ use crate::{
 ///
 ///
 /// [`Aio`]: struct.Aio.html
+/// [`Message::copy_header_from`]: struct.Message.html#method.copy_header_from
+/// [`RawSocket`]: struct.RawSocket.html
 #[derive(Clone, Debug)]
 pub struct Context
 {
@@ -80,6 +110,26 @@ impl Context
 	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
 	pub fn recv(&self, aio: &Aio) -> Result<()> { aio.recv_ctx(self) }
 
+	/// Returns a future that receives a message on the context.
+	///
+	/// The future owns its own `Aio` object and a handle to the context, which
+	/// keeps the two together across await points. The receive is started
+	/// when the future is first polled and is canceled if the future is
+	/// dropped before completing.
+	pub fn recv_future(&self) -> RecvFuture { RecvFuture::new(Source::Context(self.clone())) }
+
+	/// Returns a future that sends the message on the context.
+	///
+	/// The future owns its own `Aio` object and a handle to the context, which
+	/// keeps the two together across await points. The send is started when
+	/// the future is first polled and is canceled if the future is dropped
+	/// before completing. If the message could not be sent, it is returned as
+	/// part of the error.
+	pub fn send_future<M: Into<Message>>(&self, msg: M) -> SendFuture
+	{
+		SendFuture::new(Source::Context(self.clone()), msg.into())
+	}
+
 	/// Closes the context.
 	///
 	/// Messages that have been submitted for sending may be flushed or
@@ -90,12 +140,192 @@ impl Context
 	///
 	/// Closing the owning socket also closes this context. Additionally, the
 	/// context is closed once all handles have been dropped.
+	///
+	/// Any operation running on an [`Aio`] with this context is aborted and
+	/// its callback will receive [`Closed`]. This makes closing a context a
+	/// simple way to stop a single worker without affecting the others.
+	/// Because an `Aio` does not hold on to the context, the two can be
+	/// dropped in either order. If the context is dropped first, the `Aio`
+	/// operation fails with [`Closed`] in the same way.
+	///
+	/// [`Aio`]: struct.Aio.html
+	/// [`Closed`]: enum.Error.html#variant.Closed
 	pub fn close(&self) { self.inner.close() }
 
 	/// Returns the inner `nng_ctx` object.
 	pub(crate) fn handle(&self) -> nng_sys::nng_ctx { self.inner.ctx }
 }
 
+/// A set of contexts on one socket, each with its own `Aio`, driven by a
+/// shared worker callback.
+///
+/// This packages the pattern shown in the [`Aio`] documentation: a number of
+/// contexts are opened on a socket, each is paired with an `Aio`, and every
+/// completion is handed to the worker along with the context and `Aio` it
+/// belongs to. The worker then starts the next operation on that pair. Since
+/// each context carries its own protocol state, this is the usual way to write
+/// a concurrent _rep_ or _respondent_ server.
+///
+/// The worker follows the same rules as any other `Aio` callback. It must not
+/// block, may run on several NNG threads at once, and aborts the process if it
+/// panics.
+///
+/// ## Example
+///
+/// ```
+/// use nng::{AioResult, ContextPool, Protocol, Socket};
+///
+/// let server = Socket::new(Protocol::Rep0).unwrap();
+/// let pool = ContextPool::new(&server, 4, |ctx, aio, res| match res {
+///     // Echo every request back to the client.
+///     AioResult::Recv(Ok(msg)) => ctx.send(aio, msg).map_err(|(_, e)| e).unwrap(),
+///     AioResult::Send(Ok(_)) => ctx.recv(aio).unwrap(),
+///
+///     // Stop the worker once the socket has been closed.
+///     _ => {},
+/// })
+/// .unwrap();
+///
+/// server.listen("inproc://nng/ctx/pool").unwrap();
+/// pool.start().unwrap();
+///
+/// let client = Socket::new(Protocol::Req0).unwrap();
+/// client.dial("inproc://nng/ctx/pool").unwrap();
+/// assert_eq!(&client.request(&b"hello"[..]).unwrap()[..], b"hello");
+///
+/// server.close();
+/// pool.join();
+/// ```
+///
+/// [`Aio`]: struct.Aio.html
+#[derive(Debug)]
+pub struct ContextPool
+{
+	/// The contexts and their `Aio` objects.
+	workers: Vec<(Context, Aio)>,
+}
+impl ContextPool
+{
+	/// Opens `count` contexts on the socket, each with its own `Aio`.
+	///
+	/// No operations are started until [`start`] is called, which allows the
+	/// socket to be configured and bound in the meantime.
+	///
+	/// # Errors
+	///
+	/// * [`NotSupported`]: The protocol does not support separate contexts or the socket was opened in raw mode.
+	/// * [`OutOfMemory`]: Insufficient memory is available.
+	///
+	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	/// [`start`]: #method.start
+	pub fn new<F>(socket: &Socket, count: usize, worker: F) -> Result<Self>
+	where
+		F: Fn(&Context, &Aio, AioResult) + Send + Sync + 'static,
+	{
+		let worker = Arc::new(worker);
+		let workers = (0..count)
+			.map(|_| {
+				let ctx = Context::new(socket)?;
+				let ctx_clone = ctx.clone();
+				let worker = Arc::clone(&worker);
+
+				let aio = Aio::new(move |aio, res| worker(&ctx_clone, &aio, res))?;
+				Ok((ctx, aio))
+			})
+			.collect::<Result<_>>()?;
+
+		Ok(ContextPool { workers })
+	}
+
+	/// Starts a receive operation on every context.
+	///
+	/// # Errors
+	///
+	/// * [`IncorrectState`]: One of the `Aio` objects already has a running operation.
+	///
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
+	pub fn start(&self) -> Result<()>
+	{
+		self.workers.iter().map(|(ctx, aio)| ctx.recv(aio)).collect()
+	}
+
+	/// Blocks until none of the workers has an operation running.
+	///
+	/// This returns once every worker has stopped starting new operations,
+	/// which typically happens after the socket or the contexts are closed and
+	/// the worker does not restart after the resulting [`Closed`] error. This
+	/// must not be called from within the worker.
+	///
+	/// Each `Aio` is waited on before it is checked. Waiting on an `Aio` also
+	/// waits for its completion callback to return, so a worker that starts
+	/// another operation from the callback is always seen as busy and is
+	/// waited on again. When this returns, no worker callback is running.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{AioResult, ContextPool, Protocol, Socket};
+	/// use std::sync::{
+	///     atomic::{AtomicUsize, Ordering},
+	///     Arc,
+	/// };
+	///
+	/// let stopped = Arc::new(AtomicUsize::new(0));
+	/// let worker_stopped = Arc::clone(&stopped);
+	///
+	/// let server = Socket::new(Protocol::Rep0).unwrap();
+	/// let pool = ContextPool::new(&server, 4, move |ctx, aio, res| match res {
+	///     // Each completion starts the next operation from within the callback.
+	///     AioResult::Recv(Ok(msg)) => ctx.send(aio, msg).map_err(|(_, e)| e).unwrap(),
+	///     AioResult::Send(Ok(_)) => ctx.recv(aio).unwrap(),
+	///     _ => {
+	///         worker_stopped.fetch_add(1, Ordering::SeqCst);
+	///     },
+	/// })
+	/// .unwrap();
+	///
+	/// server.listen("inproc://nng/ctx/pool/join").unwrap();
+	/// pool.start().unwrap();
+	///
+	/// let client = Socket::new(Protocol::Req0).unwrap();
+	/// client.dial("inproc://nng/ctx/pool/join").unwrap();
+	/// for _ in 0..8 {
+	///     client.request(&b"ping"[..]).unwrap();
+	/// }
+	///
+	/// // Every worker has finished its final callback once `join` returns.
+	/// server.close();
+	/// pool.join();
+	/// assert_eq!(stopped.load(Ordering::SeqCst), 4);
+	/// ```
+	///
+	/// [`Closed`]: enum.Error.html#variant.Closed
+	pub fn join(&self)
+	{
+		for (_, aio) in &self.workers {
+			aio.wait();
+			while aio.busy() {
+				aio.wait();
+			}
+		}
+	}
+
+	/// Closes all of the contexts in the pool.
+	///
+	/// Running operations complete with [`Closed`] and the socket itself is
+	/// left open.
+	///
+	/// [`Closed`]: enum.Error.html#variant.Closed
+	pub fn close(&self) { self.workers.iter().for_each(|(ctx, _)| ctx.close()) }
+
+	/// Returns the number of contexts in the pool.
+	pub fn len(&self) -> usize { self.workers.len() }
+
+	/// Returns true if the pool has no contexts.
+	pub fn is_empty(&self) -> bool { self.workers.is_empty() }
+}
+
 #[cfg(feature = "ffi-module")]
 impl Context
 {
@@ -159,8 +389,12 @@ expose_options!{
 	SETOPT_SIZE = nng_sys::nng_ctx_set_size;
 	SETOPT_STRING = nng_sys::nng_ctx_set_string;
 
-	Gets -> [protocol::reqrep::ResendTime, protocol::survey::SurveyTime];
-	Sets -> [protocol::reqrep::ResendTime, protocol::survey::SurveyTime];
+	Gets -> [RecvTimeout, SendTimeout,
+	         protocol::reqrep::ResendTime,
+	         protocol::survey::SurveyTime];
+	Sets -> [RecvTimeout, SendTimeout,
+	         protocol::reqrep::ResendTime,
+	         protocol::survey::SurveyTime];
 }
 
 /// A wrapper around an `nng_ctx`.
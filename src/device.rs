@@ -23,6 +23,8 @@ use crate::{
 ///
 /// This function does not return unless one of the sockets encounters an
 /// error or is closed. For more information see the [NNG documentation][1].
+/// As such, the forwarder is typically run on a dedicated thread and stopped
+/// by closing one of the sockets.
 ///
 /// # Errors
 ///
@@ -33,6 +35,38 @@ use crate::{
 /// * [`InvalidInput`]: Sockets are not compatible or both are invalid.
 /// * [`OutOfMemory`]: Insufficient memory available.
 ///
+/// # Example
+///
+/// A request/reply proxy, where clients dial the front-end and services dial
+/// the back-end:
+///
+/// ```
+/// use std::thread;
+/// use nng::{Protocol, RawSocket, Socket};
+///
+/// let front = RawSocket::new(Protocol::Rep0).unwrap();
+/// front.socket.listen("inproc://nng/device/front").unwrap();
+///
+/// let back = RawSocket::new(Protocol::Req0).unwrap();
+/// back.socket.listen("inproc://nng/device/back").unwrap();
+///
+/// let proxy = thread::spawn(move || nng::forwarder(front, back));
+///
+/// let service = Socket::new(Protocol::Rep0).unwrap();
+/// service.dial("inproc://nng/device/back").unwrap();
+///
+/// let client = Socket::new(Protocol::Req0).unwrap();
+/// client.dial("inproc://nng/device/front").unwrap();
+/// client.send(&b"ping"[..]).unwrap();
+///
+/// let req = service.recv().unwrap();
+/// assert_eq!(&req[..], b"ping");
+/// service.send(&b"pong"[..]).unwrap();
+///
+/// assert_eq!(&client.recv().unwrap()[..], b"pong");
+/// # drop(proxy);
+/// ```
+///
 /// [1]: https://nanomsg.github.io/nng/man/v1.2.2/nng_device.3
 /// [`Closed`]: enum.Error.html#variant.Closed
 /// [`InvalidInput`]: enum.Error.html#variant.InvalidInput
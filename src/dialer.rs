@@ -7,6 +7,7 @@ use std::{
 
 use crate::{
 	error::{Error, Result},
+	options::{Options, RecvMaxSize},
 	socket::Socket,
 };
 
@@ -53,6 +54,7 @@ impl Dialer
 	/// * [`ConnectionRefused`]: The remote peer refused the connection.
 	/// * [`ConnectionReset`]: The remote peer reset the connection.
 	/// * [`DestUnreachable`]: The remote address is not reachable.
+	/// * [`IncorrectState`]: The socket requires a receive size limit and none is set.
 	/// * [`OutOfMemory`]: Insufficient memory is available.
 	/// * [`PeerAuth`]: Authentication or authorization failure.
 	/// * [`Protocol`]: A protocol error occurred.
@@ -64,7 +66,8 @@ impl Dialer
 	/// [`ConnectionRefused`]: enum.Error.html#variant.ConnectionRefused
 	/// [`ConnectionReset`]: enum.Error.html#variant.ConnectionReset
 	/// [`DestUnreachable`]: enum.Error.html#variant.DestUnreachable
-	/// [`DialerBuilder`]: struct.DailerOptions.html
+	/// [`DialerBuilder`]: struct.DialerBuilder.html
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
 	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
 	/// [`PeerAuth`]: enum.Error.html#variant.PeerAuth
 	/// [`Protocol`]: enum.Error.html#variant.Protocol
@@ -74,6 +77,7 @@ impl Dialer
 		// creating the dialer will far outweigh the cost of allocating a
 		// single string. Having a full Rust interface will make it easier to
 		// work with.
+		socket.check_recv_limit()?;
 		let addr = CString::new(url).map_err(|_| Error::AddressInvalid)?;
 		let mut handle = nng_sys::nng_dialer::NNG_DIALER_INITIALIZER;
 		let flags = if nonblocking { nng_sys::NNG_FLAG_NONBLOCK } else { 0 };
@@ -203,6 +207,27 @@ expose_options!{
 /// dialer without closing the socket, then [`Socket::dial`] provides a simpler
 /// interface.
 ///
+/// ## Example
+///
+/// ```
+/// use std::time::Duration;
+/// use nng::{
+///     options::{Options, ReconnectMaxTime, ReconnectMinTime},
+///     DialerBuilder, Protocol, Socket,
+/// };
+///
+/// let socket = Socket::new(Protocol::Req0).unwrap();
+/// let builder = DialerBuilder::new(&socket, "inproc://nng/dialer/example").unwrap();
+///
+/// // The options are applied before the first connection attempt is made.
+/// builder.set_opt::<ReconnectMinTime>(Some(Duration::from_millis(100))).unwrap();
+/// builder.set_opt::<ReconnectMaxTime>(Some(Duration::from_secs(5))).unwrap();
+///
+/// // Nothing is listening on the address yet, so don't wait for the connection.
+/// let dialer = builder.start(true).map_err(|(_, e)| e).unwrap();
+/// dialer.close();
+/// ```
+///
 ///
 /// [`Socket::dial`]: struct.Socket.html#method.dial
 #[derive(Debug)]
@@ -210,6 +235,9 @@ pub struct DialerBuilder
 {
 	/// The underlying dialer object that we are configuring
 	handle: nng_sys::nng_dialer,
+
+	/// Whether the socket required a receive size limit when this was created.
+	require_recv_limit: bool,
 }
 impl DialerBuilder
 {
@@ -241,7 +269,7 @@ impl DialerBuilder
 			nng_sys::nng_dialer_create(&mut handle as *mut _, socket.handle(), addr.as_ptr())
 		};
 
-		rv2res!(rv, DialerBuilder { handle })
+		rv2res!(rv, DialerBuilder { handle, require_recv_limit: socket.recv_limit_required() })
 	}
 
 	/// Cause the dialer to start connecting to the address with which it was
@@ -267,6 +295,7 @@ impl DialerBuilder
 	/// * [`ConnectionRefused`]: The remote peer refused the connection.
 	/// * [`ConnectionReset`]: The remote peer reset the connection.
 	/// * [`DestUnreachable`]: The remote address is not reachable.
+	/// * [`IncorrectState`]: The socket requires a receive size limit and none is set.
 	/// * [`OutOfMemory`]: Insufficient memory available.
 	/// * [`PeerAuth`]: Authentication or authorization failure.
 	/// * [`Protocol`]: A protocol error occurred.
@@ -276,6 +305,7 @@ impl DialerBuilder
 	/// [`ConnectionRefused`]: enum.Error.html#variant.ConnectionRefused
 	/// [`ConnectionReset`]: enum.Error.html#variant.ConnectionReset
 	/// [`DestUnreachable`]: enum.Error.html#variant.DestUnreachable
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
 	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
 	/// [`PeerAuth`]: enum.Error.html#variant.PeerAuth
 	/// [`Protocol`]: enum.Error.html#variant.Protocol
@@ -283,6 +313,15 @@ impl DialerBuilder
 	{
 		let flags = if nonblocking { nng_sys::NNG_FLAG_NONBLOCK } else { 0 };
 
+		// The dialer inherits the limit of the socket unless it was given its own.
+		if self.require_recv_limit {
+			match self.get_opt::<RecvMaxSize>() {
+				Ok(0) => return Err((self, Error::IncorrectState)),
+				Err(e) => return Err((self, e)),
+				Ok(_) => {},
+			}
+		}
+
 		// If there is an error starting the dialer, we don't want to consume
 		// it. Instead, we'll return it to the user and they can decide what to
 		// do.
@@ -341,6 +380,7 @@ expose_options!{
 	         transport::tcp::KeepAlive,
 	         transport::tls::CaFile,
 	         transport::tls::CertKeyFile,
+	         transport::tls::Config,
 	         transport::websocket::RequestHeaders,
 	         transport::websocket::Protocol];
 }
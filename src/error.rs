@@ -47,6 +47,9 @@ pub enum Error
 	AddressInUse,
 
 	/// The resource is not in the appropriate state for the operation
+	///
+	/// A _surveyor_ socket also reports this when receiving after the survey
+	/// time has expired, which means that the survey has ended normally.
 	IncorrectState,
 
 	/// Entry was not found
@@ -0,0 +1,260 @@
+use std::{
+	future::Future,
+	pin::Pin,
+	sync::{Arc, Mutex},
+	task::{Context as TaskContext, Poll, Waker},
+};
+
+use crate::{
+	aio::{Aio, AioResult},
+	ctx::Context,
+	error::{Result, SendResult},
+	message::Message,
+	socket::Socket,
+};
+
+/// A future that resolves to a message received on a socket or context.
+///
+/// The receive operation is started the first time the future is polled. If
+/// the future is dropped before it completes, the operation is canceled.
+///
+/// This type is created by the [`Socket::recv_future`] and
+/// [`Context::recv_future`] functions.
+///
+/// [`Context::recv_future`]: struct.Context.html#method.recv_future
+/// [`Socket::recv_future`]: struct.Socket.html#method.recv_future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct RecvFuture
+{
+	/// The object on which the message is received.
+	source: Source,
+
+	/// The progress of the operation.
+	state: State,
+}
+impl RecvFuture
+{
+	/// Creates a new future that receives from the specified source.
+	pub(crate) const fn new(source: Source) -> Self { RecvFuture { source, state: State::Idle } }
+}
+
+impl Future for RecvFuture
+{
+	type Output = Result<Message>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut TaskContext) -> Poll<Self::Output>
+	{
+		let this = self.get_mut();
+
+		if let State::Idle = this.state {
+			let op = match Operation::new(cx.waker()) {
+				Ok(op) => op,
+				Err(e) => return Poll::Ready(Err(e)),
+			};
+
+			if let Err(e) = this.source.recv(&op.aio) {
+				return Poll::Ready(Err(e));
+			}
+
+			this.state = State::Running(op);
+			return Poll::Pending;
+		}
+
+		match this.state.poll(cx) {
+			Poll::Ready(AioResult::Recv(res)) => Poll::Ready(res),
+			Poll::Ready(_) => unreachable!("receive operation produced a non-receive result"),
+			Poll::Pending => Poll::Pending,
+		}
+	}
+}
+
+/// A future that resolves once a message has been sent on a socket or context.
+///
+/// The send operation is started the first time the future is polled. If the
+/// future is dropped before it completes, the operation is canceled. If the
+/// message could not be sent, it is returned as part of the error.
+///
+/// This type is created by the [`Socket::send_future`] and
+/// [`Context::send_future`] functions.
+///
+/// [`Context::send_future`]: struct.Context.html#method.send_future
+/// [`Socket::send_future`]: struct.Socket.html#method.send_future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct SendFuture
+{
+	/// The object on which the message is sent.
+	source: Source,
+
+	/// The message to send, until the operation is started.
+	msg: Option<Message>,
+
+	/// The progress of the operation.
+	state: State,
+}
+impl SendFuture
+{
+	/// Creates a new future that sends the message on the specified source.
+	pub(crate) const fn new(source: Source, msg: Message) -> Self
+	{
+		SendFuture { source, msg: Some(msg), state: State::Idle }
+	}
+}
+
+impl Future for SendFuture
+{
+	type Output = SendResult<()>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut TaskContext) -> Poll<Self::Output>
+	{
+		let this = self.get_mut();
+
+		if let State::Idle = this.state {
+			let msg = this.msg.take().expect("SendFuture polled after completion");
+			let op = match Operation::new(cx.waker()) {
+				Ok(op) => op,
+				Err(e) => return Poll::Ready(Err((msg, e))),
+			};
+
+			if let Err(e) = this.source.send(&op.aio, msg) {
+				return Poll::Ready(Err(e));
+			}
+
+			this.state = State::Running(op);
+			return Poll::Pending;
+		}
+
+		match this.state.poll(cx) {
+			Poll::Ready(AioResult::Send(res)) => Poll::Ready(res),
+			Poll::Ready(_) => unreachable!("send operation produced a non-send result"),
+			Poll::Pending => Poll::Pending,
+		}
+	}
+}
+
+/// The object on which a future performs its operation.
+#[derive(Debug)]
+pub(crate) enum Source
+{
+	/// The operation is performed directly on a socket.
+	Socket(Socket),
+
+	/// The operation is performed on a socket context.
+	Context(Context),
+}
+impl Source
+{
+	/// Starts a receive operation on the `Aio`.
+	fn recv(&self, aio: &Aio) -> Result<()>
+	{
+		match self {
+			Source::Socket(s) => s.recv_async(aio),
+			Source::Context(c) => c.recv(aio),
+		}
+	}
+
+	/// Starts a send operation on the `Aio`.
+	fn send(&self, aio: &Aio, msg: Message) -> SendResult<()>
+	{
+		match self {
+			Source::Socket(s) => s.send_async(aio, msg),
+			Source::Context(c) => c.send(aio, msg),
+		}
+	}
+}
+
+/// The progress of a future.
+#[derive(Debug)]
+enum State
+{
+	/// The operation has not yet been started.
+	Idle,
+
+	/// The operation is in progress.
+	Running(Operation),
+
+	/// The operation has completed and the result was handed out.
+	Done,
+}
+impl State
+{
+	/// Checks whether the running operation has completed.
+	fn poll(&mut self, cx: &mut TaskContext) -> Poll<AioResult>
+	{
+		let res = match self {
+			State::Running(op) => {
+				let mut shared = op.shared.lock().unwrap();
+				match shared.result.take() {
+					Some(res) => res,
+					None => {
+						shared.waker = Some(cx.waker().clone());
+						return Poll::Pending;
+					},
+				}
+			},
+			State::Idle | State::Done => panic!("future polled after completion"),
+		};
+
+		*self = State::Done;
+		Poll::Ready(res)
+	}
+}
+
+/// An asynchronous operation that is currently running.
+#[derive(Debug)]
+struct Operation
+{
+	/// The AIO object performing the operation.
+	aio: Aio,
+
+	/// The state shared with the AIO callback.
+	shared: Arc<Mutex<Shared>>,
+}
+impl Operation
+{
+	/// Creates a new AIO object that wakes the task when it completes.
+	fn new(waker: &Waker) -> Result<Self>
+	{
+		let shared = Arc::new(Mutex::new(Shared { result: None, waker: Some(waker.clone()) }));
+		let cb_shared = Arc::clone(&shared);
+
+		let aio = Aio::new(move |_, res| {
+			let waker = {
+				let mut shared = cb_shared.lock().unwrap();
+				shared.result = Some(res);
+				shared.waker.take()
+			};
+
+			if let Some(w) = waker {
+				w.wake();
+			}
+		})?;
+
+		Ok(Operation { aio, shared })
+	}
+}
+
+impl Drop for Operation
+{
+	fn drop(&mut self)
+	{
+		// If the operation is still running, we need to cancel it and then wait
+		// for the callback to finish. Otherwise, the callback might run after the
+		// AIO has started dropping and any message it owns would be leaked. Both
+		// calls return immediately if the operation has already completed.
+		self.aio.cancel();
+		self.aio.wait();
+	}
+}
+
+/// The state shared between a future and its AIO callback.
+#[derive(Debug)]
+struct Shared
+{
+	/// The result of the operation, once it has completed.
+	result: Option<AioResult>,
+
+	/// The waker of the task waiting on the operation.
+	waker: Option<Waker>,
+}
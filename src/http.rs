@@ -0,0 +1,683 @@
+use std::{
+	ffi::{CStr, CString},
+	fmt,
+	marker::PhantomData,
+	mem::MaybeUninit,
+	os::raw::{c_char, c_void},
+	ptr::{self, NonNull},
+	slice,
+};
+
+use crate::{
+	addr::SocketAddr,
+	aio::RawAio,
+	error::{Error, Result},
+	tls::TlsConfig,
+	url::Url,
+	util::{abort_unwind, validate_ptr},
+};
+
+/// A minimal HTTP client built on top of NNG's HTTP subsystem.
+///
+/// Each request opens a new connection to the server, sends the request and
+/// waits for the complete response. The transaction is run by NNG on its own
+/// I/O threads, so the client shares the event loop (and, for `https`, the
+/// TLS configuration) with the rest of the application's messaging code.
+///
+/// See the [NNG documentation][1] for more information.
+///
+/// ## Example
+///
+/// ```
+/// use nng::{HttpClient, HttpServer, SocketAddr, Url};
+///
+/// // Serve a couple of endpoints on a free port of the loopback interface.
+/// let server = HttpServer::new(&Url::parse("http://127.0.0.1:0").unwrap()).unwrap();
+/// server.add_handler("/status", "GET", |_| (200, b"running".to_vec())).unwrap();
+/// server.add_handler("/echo", "POST", |req| (200, req.body().to_vec())).unwrap();
+/// server.start().unwrap();
+///
+/// let base = match server.local_addr().unwrap() {
+///     SocketAddr::Inet(addr) => format!("http://{}", addr),
+///     addr => panic!("unexpected address {}", addr),
+/// };
+///
+/// let url = Url::parse(&format!("{}/status", base)).unwrap();
+/// let client = HttpClient::new(&url).unwrap();
+///
+/// let res = client.get(&url).unwrap();
+/// assert_eq!(res.status(), 200);
+/// assert_eq!(res.body(), b"running");
+///
+/// let url = Url::parse(&format!("{}/echo", base)).unwrap();
+/// let res = client.post(&url, "text/plain", b"hello").unwrap();
+/// assert_eq!(res.status(), 200);
+/// assert_eq!(res.into_body(), b"hello");
+///
+/// server.stop();
+/// ```
+///
+/// [1]: https://nanomsg.github.io/nng/man/v1.2.2/nng_http_client.5.html
+pub struct HttpClient
+{
+	/// The underlying NNG HTTP client.
+	client: NonNull<nng_sys::nng_http_client>,
+}
+impl HttpClient
+{
+	/// Creates a new client for the server at the specified URL.
+	///
+	/// Only the scheme, host and port of the URL are used. The scheme must be
+	/// either `http` or `https`.
+	///
+	/// # Errors
+	///
+	/// * [`AddressInvalid`]: The URL is not a valid HTTP URL.
+	/// * [`NotSupported`]: HTTP (or TLS, for `https`) support is not available.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// [`AddressInvalid`]: enum.Error.html#variant.AddressInvalid
+	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	pub fn new(url: &Url) -> Result<HttpClient>
+	{
+		let mut client: *mut nng_sys::nng_http_client = ptr::null_mut();
+		let rv = unsafe { nng_sys::nng_http_client_alloc(&mut client as *mut _, url.as_ptr()) };
+
+		validate_ptr(rv, client).map(|client| HttpClient { client })
+	}
+
+	/// Sets the TLS configuration used for `https` connections.
+	///
+	/// # Errors
+	///
+	/// * [`NotSupported`]: TLS support is not available.
+	///
+	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+	pub fn set_tls(&self, config: &TlsConfig) -> Result<()>
+	{
+		let rv = unsafe { nng_sys::nng_http_client_set_tls(self.client.as_ptr(), config.as_ptr()) };
+		rv2res!(rv)
+	}
+
+	/// Performs a `GET` request for the specified URL.
+	///
+	/// # Errors
+	///
+	/// See [`request`](#method.request).
+	pub fn get(&self, url: &Url) -> Result<HttpResponse> { self.request("GET", url, &[], &[]) }
+
+	/// Performs a `POST` request for the specified URL with the given body.
+	///
+	/// # Errors
+	///
+	/// See [`request`](#method.request).
+	pub fn post(&self, url: &Url, content_type: &str, body: &[u8]) -> Result<HttpResponse>
+	{
+		self.request("POST", url, &[("Content-Type", content_type)], body)
+	}
+
+	/// Performs an HTTP request and waits for the response.
+	///
+	/// The request is sent to the server this client was created for, using the
+	/// path and query of the provided URL. The `Content-Length` header is set
+	/// automatically from the body.
+	///
+	/// # Errors
+	///
+	/// * [`Closed`]: The connection was closed before the response arrived.
+	/// * [`ConnectionRefused`]: The server refused the connection.
+	/// * [`InvalidInput`]: The method or a header contains a nul byte.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	/// * [`Protocol`]: The server sent an invalid response.
+	/// * [`TimedOut`]: The operation timed out.
+	///
+	/// [`Closed`]: enum.Error.html#variant.Closed
+	/// [`ConnectionRefused`]: enum.Error.html#variant.ConnectionRefused
+	/// [`InvalidInput`]: enum.Error.html#variant.InvalidInput
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	/// [`Protocol`]: enum.Error.html#variant.Protocol
+	/// [`TimedOut`]: enum.Error.html#variant.TimedOut
+	pub fn request(
+		&self,
+		method: &str,
+		url: &Url,
+		headers: &[(&str, &str)],
+		body: &[u8],
+	) -> Result<HttpResponse>
+	{
+		let req = ClientRequest::new(url)?;
+		req.set_method(method)?;
+		for (key, val) in headers {
+			req.set_header(key, val)?;
+		}
+		if !body.is_empty() {
+			req.copy_data(body)?;
+		}
+
+		let res = HttpResponse::new()?;
+		let aio = RawAio::new()?;
+		unsafe {
+			nng_sys::nng_http_client_transact(
+				self.client.as_ptr(),
+				req.req.as_ptr(),
+				res.res.as_ptr(),
+				aio.as_ptr(),
+			);
+		}
+		aio.wait()?;
+
+		Ok(res)
+	}
+}
+
+impl fmt::Debug for HttpClient
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		f.debug_struct("HttpClient").field("client", &self.client).finish()
+	}
+}
+
+impl Drop for HttpClient
+{
+	fn drop(&mut self)
+	{
+		unsafe { nng_sys::nng_http_client_free(self.client.as_ptr()) }
+	}
+}
+
+// NNG protects the client with its own lock and every transaction uses its own
+// connection, request and response objects.
+unsafe impl Send for HttpClient {}
+unsafe impl Sync for HttpClient {}
+
+/// The response to a request made by an [`HttpClient`].
+///
+/// [`HttpClient`]: struct.HttpClient.html
+pub struct HttpResponse
+{
+	/// The underlying NNG HTTP response.
+	res: NonNull<nng_sys::nng_http_res>,
+}
+impl HttpResponse
+{
+	/// Allocates a new, empty response.
+	fn new() -> Result<HttpResponse>
+	{
+		let mut res: *mut nng_sys::nng_http_res = ptr::null_mut();
+		let rv = unsafe { nng_sys::nng_http_res_alloc(&mut res as *mut _) };
+
+		validate_ptr(rv, res).map(|res| HttpResponse { res })
+	}
+
+	/// Returns the status code of the response.
+	pub fn status(&self) -> u16 { unsafe { nng_sys::nng_http_res_get_status(self.res.as_ptr()) } }
+
+	/// Returns the reason phrase sent with the status code.
+	pub fn reason(&self) -> &str
+	{
+		unsafe { to_str(nng_sys::nng_http_res_get_reason(self.res.as_ptr())) }.unwrap_or("")
+	}
+
+	/// Returns the value of the named header, if the server sent it.
+	///
+	/// Header names are compared case-insensitively. NNG does not provide a way
+	/// to enumerate the headers of a response, so they can only be looked up by
+	/// name.
+	pub fn header(&self, name: &str) -> Option<&str>
+	{
+		let name = CString::new(name).ok()?;
+		unsafe { to_str(nng_sys::nng_http_res_get_header(self.res.as_ptr(), name.as_ptr())) }
+	}
+
+	/// Returns the body of the response.
+	pub fn body(&self) -> &[u8]
+	{
+		let mut data: *mut c_void = ptr::null_mut();
+		let mut size = 0;
+		unsafe {
+			nng_sys::nng_http_res_get_data(self.res.as_ptr(), &mut data as *mut _, &mut size as *mut _);
+			if data.is_null() { &[] } else { slice::from_raw_parts(data as *const u8, size) }
+		}
+	}
+
+	/// Consumes the response and returns a copy of its body.
+	pub fn into_body(self) -> Vec<u8> { self.body().to_vec() }
+}
+
+impl fmt::Debug for HttpResponse
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		f.debug_struct("HttpResponse")
+			.field("status", &self.status())
+			.field("reason", &self.reason())
+			.field("body_len", &self.body().len())
+			.finish()
+	}
+}
+
+impl Drop for HttpResponse
+{
+	fn drop(&mut self)
+	{
+		unsafe { nng_sys::nng_http_res_free(self.res.as_ptr()) }
+	}
+}
+
+// The response is exclusively owned by this type once the transaction that
+// filled it in has completed.
+unsafe impl Send for HttpResponse {}
+unsafe impl Sync for HttpResponse {}
+
+/// An HTTP request that is being prepared by an `HttpClient`.
+struct ClientRequest
+{
+	/// The underlying NNG HTTP request.
+	req: NonNull<nng_sys::nng_http_req>,
+}
+impl ClientRequest
+{
+	/// Allocates a new request for the specified URL.
+	fn new(url: &Url) -> Result<ClientRequest>
+	{
+		let mut req: *mut nng_sys::nng_http_req = ptr::null_mut();
+		let rv = unsafe { nng_sys::nng_http_req_alloc(&mut req as *mut _, url.as_ptr()) };
+
+		validate_ptr(rv, req).map(|req| ClientRequest { req })
+	}
+
+	/// Sets the method of the request.
+	fn set_method(&self, method: &str) -> Result<()>
+	{
+		let method = CString::new(method).map_err(|_| Error::InvalidInput)?;
+		let rv = unsafe { nng_sys::nng_http_req_set_method(self.req.as_ptr(), method.as_ptr()) };
+
+		rv2res!(rv)
+	}
+
+	/// Sets a header of the request, replacing any previous value.
+	fn set_header(&self, key: &str, val: &str) -> Result<()>
+	{
+		let key = CString::new(key).map_err(|_| Error::InvalidInput)?;
+		let val = CString::new(val).map_err(|_| Error::InvalidInput)?;
+		let rv = unsafe {
+			nng_sys::nng_http_req_set_header(self.req.as_ptr(), key.as_ptr(), val.as_ptr())
+		};
+
+		rv2res!(rv)
+	}
+
+	/// Copies the provided bytes into the body of the request.
+	fn copy_data(&self, data: &[u8]) -> Result<()>
+	{
+		let rv = unsafe {
+			nng_sys::nng_http_req_copy_data(self.req.as_ptr(), data.as_ptr() as _, data.len())
+		};
+
+		rv2res!(rv)
+	}
+}
+
+impl Drop for ClientRequest
+{
+	fn drop(&mut self)
+	{
+		unsafe { nng_sys::nng_http_req_free(self.req.as_ptr()) }
+	}
+}
+
+/// Represents the type of the handler closures registered with a server.
+type HandlerCallback = Box<dyn Fn(&HttpRequest) -> (u16, Vec<u8>) + Send + Sync + 'static>;
+
+/// A minimal HTTP server built on top of NNG's HTTP subsystem.
+///
+/// Requests are dispatched to the handlers registered with [`add_handler`]
+/// based on their path and method. Handlers are run on NNG's I/O threads and
+/// must not block for long periods of time.
+///
+/// NNG shares a single server between all users of the same address, so
+/// creating multiple `HttpServer` objects for the same URL (or using the same
+/// address with the `ws://` transport) will refer to the same server.
+///
+/// See the [NNG documentation][1] for more information.
+///
+/// ## Example
+///
+/// ```
+/// use nng::{HttpClient, HttpServer, SocketAddr, Url};
+///
+/// // Port 0 lets the OS pick a free port, which is read back once listening.
+/// let server = HttpServer::new(&Url::parse("http://127.0.0.1:0").unwrap()).unwrap();
+///
+/// server.add_handler("/health", "GET", |_| (200, b"OK".to_vec())).unwrap();
+/// server
+///     .add_handler("/greet", "POST", |req| {
+///         let mut body = b"hello, ".to_vec();
+///         body.extend_from_slice(req.body());
+///         (201, body)
+///     })
+///     .unwrap();
+/// server.start().unwrap();
+///
+/// let base = match server.local_addr().unwrap() {
+///     SocketAddr::Inet(addr) => format!("http://{}", addr),
+///     addr => panic!("unexpected address {}", addr),
+/// };
+///
+/// // Make requests against the server over the loopback interface.
+/// let url = Url::parse(&base).unwrap();
+/// let client = HttpClient::new(&url).unwrap();
+///
+/// let health = Url::parse(&format!("{}/health", base)).unwrap();
+/// let res = client.get(&health).unwrap();
+/// assert_eq!((res.status(), res.body()), (200, &b"OK"[..]));
+///
+/// let greet = Url::parse(&format!("{}/greet", base)).unwrap();
+/// let res = client.post(&greet, "text/plain", b"world").unwrap();
+/// assert_eq!((res.status(), res.body()), (201, &b"hello, world"[..]));
+///
+/// // Paths without a handler are answered by NNG itself.
+/// let missing = Url::parse(&format!("{}/missing", base)).unwrap();
+/// assert_eq!(client.get(&missing).unwrap().status(), 404);
+///
+/// server.stop();
+/// ```
+///
+/// [1]: https://nanomsg.github.io/nng/man/v1.2.2/nng_http_server.5.html
+/// [`add_handler`]: #method.add_handler
+pub struct HttpServer
+{
+	/// The underlying NNG HTTP server.
+	server: NonNull<nng_sys::nng_http_server>,
+}
+impl HttpServer
+{
+	/// Creates a new server bound to the specified URL.
+	///
+	/// Only the scheme, host and port of the URL are used. The server does not
+	/// accept connections until it is [started].
+	///
+	/// # Errors
+	///
+	/// * [`AddressInvalid`]: The URL is not a valid HTTP URL.
+	/// * [`NotSupported`]: HTTP (or TLS, for `https`) support is not available.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// [started]: #method.start
+	/// [`AddressInvalid`]: enum.Error.html#variant.AddressInvalid
+	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	pub fn new(url: &Url) -> Result<HttpServer>
+	{
+		let mut server: *mut nng_sys::nng_http_server = ptr::null_mut();
+		let rv = unsafe { nng_sys::nng_http_server_hold(&mut server as *mut _, url.as_ptr()) };
+
+		validate_ptr(rv, server).map(|server| HttpServer { server })
+	}
+
+	/// Registers a handler for requests to the given path and method.
+	///
+	/// The handler receives the request and returns the status code and body of
+	/// the response. If the handler panics, the process is aborted, as unwinding
+	/// into NNG is undefined behavior.
+	///
+	/// # Errors
+	///
+	/// * [`AddressInUse`]: A handler is already registered for the path.
+	/// * [`InvalidInput`]: The path or method contains a nul byte.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// [`AddressInUse`]: enum.Error.html#variant.AddressInUse
+	/// [`InvalidInput`]: enum.Error.html#variant.InvalidInput
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	pub fn add_handler<F>(&self, path: &str, method: &str, handler: F) -> Result<()>
+	where
+		F: Fn(&HttpRequest) -> (u16, Vec<u8>) + Send + Sync + 'static,
+	{
+		let path = CString::new(path).map_err(|_| Error::InvalidInput)?;
+		let method = CString::new(method).map_err(|_| Error::InvalidInput)?;
+
+		let mut hnd: *mut nng_sys::nng_http_handler = ptr::null_mut();
+		let rv = unsafe {
+			nng_sys::nng_http_handler_alloc(
+				&mut hnd as *mut _,
+				path.as_ptr(),
+				Some(HttpServer::trampoline),
+			)
+		};
+		let hnd = validate_ptr(rv, hnd)?;
+
+		// Double box the closure so that we can pass a thin pointer to NNG. Once the
+		// data is set, NNG is responsible for dropping it when the handler is freed.
+		let callback: Box<HandlerCallback> = Box::new(Box::new(handler));
+		let callback_ptr = Box::into_raw(callback);
+		let rv = unsafe {
+			nng_sys::nng_http_handler_set_data(
+				hnd.as_ptr(),
+				callback_ptr as _,
+				Some(HttpServer::drop_callback),
+			)
+		};
+		if rv != 0 {
+			unsafe {
+				drop(Box::from_raw(callback_ptr));
+				nng_sys::nng_http_handler_free(hnd.as_ptr());
+			}
+			return rv2res!(rv);
+		}
+
+		let rv = unsafe {
+			let rv = nng_sys::nng_http_handler_set_method(hnd.as_ptr(), method.as_ptr());
+			if rv == 0 { nng_sys::nng_http_server_add_handler(self.server.as_ptr(), hnd.as_ptr()) }
+			else { rv }
+		};
+		if rv != 0 {
+			unsafe { nng_sys::nng_http_handler_free(hnd.as_ptr()) }
+		}
+
+		rv2res!(rv)
+	}
+
+	/// Sets the TLS configuration used for `https` connections.
+	///
+	/// # Errors
+	///
+	/// * [`Busy`]: The server is already running.
+	/// * [`NotSupported`]: TLS support is not available.
+	///
+	/// [`Busy`]: enum.Error.html#variant.Busy
+	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+	pub fn set_tls(&self, config: &TlsConfig) -> Result<()>
+	{
+		let rv = unsafe { nng_sys::nng_http_server_set_tls(self.server.as_ptr(), config.as_ptr()) };
+		rv2res!(rv)
+	}
+
+	/// Starts accepting connections.
+	///
+	/// The server is reference counted by NNG, so starting it multiple times
+	/// requires the same number of calls to [`stop`] before it stops listening.
+	///
+	/// # Errors
+	///
+	/// * [`AddressInUse`]: The address is already in use by another process.
+	/// * [`AddressInvalid`]: The address is not valid for listening.
+	/// * [`PermissionDenied`]: Insufficient privileges to listen on the address.
+	///
+	/// [`AddressInUse`]: enum.Error.html#variant.AddressInUse
+	/// [`AddressInvalid`]: enum.Error.html#variant.AddressInvalid
+	/// [`PermissionDenied`]: enum.Error.html#variant.PermissionDenied
+	/// [`stop`]: #method.stop
+	pub fn start(&self) -> Result<()>
+	{
+		let rv = unsafe { nng_sys::nng_http_server_start(self.server.as_ptr()) };
+		rv2res!(rv)
+	}
+
+	/// Stops accepting connections.
+	///
+	/// Connections that are already being served are closed.
+	pub fn stop(&self) { unsafe { nng_sys::nng_http_server_stop(self.server.as_ptr()) } }
+
+	/// Returns the address the server is listening on.
+	///
+	/// If the server was created with a wildcard (0) port, this is the port
+	/// that was actually bound, which makes it possible to let the OS pick a
+	/// free port.
+	///
+	/// # Errors
+	///
+	/// * [`IncorrectState`]: The server has not been started.
+	///
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
+	pub fn local_addr(&self) -> Result<SocketAddr>
+	{
+		unsafe {
+			let mut addr: MaybeUninit<nng_sys::nng_sockaddr> = MaybeUninit::uninit();
+			let rv = nng_sys::nng_http_server_get_addr(self.server.as_ptr(), addr.as_mut_ptr());
+
+			rv2res!(rv, addr.assume_init().into())
+		}
+	}
+
+	/// Trampoline function for calling a handler closure from C.
+	///
+	/// This is unsafe because you have to be absolutely positive that the
+	/// handler data is a `HandlerCallback`.
+	unsafe extern "C" fn trampoline(aio: *mut nng_sys::nng_aio)
+	{
+		abort_unwind(|| {
+			let req = nng_sys::nng_aio_get_input(aio, 0) as *mut nng_sys::nng_http_req;
+			let hnd = nng_sys::nng_aio_get_input(aio, 1) as *mut nng_sys::nng_http_handler;
+
+			let callback = nng_sys::nng_http_handler_get_data(hnd) as *const HandlerCallback;
+			assert!(!callback.is_null(), "Null handler data given to trampoline function");
+			let req = NonNull::new(req).expect("NNG provided a null HTTP request");
+
+			let (status, body) = (*callback)(&HttpRequest { req, _phantom: PhantomData });
+
+			let mut res: *mut nng_sys::nng_http_res = ptr::null_mut();
+			let mut rv = nng_sys::nng_http_res_alloc(&mut res as *mut _);
+			if rv == 0 {
+				rv = nng_sys::nng_http_res_set_status(res, status);
+			}
+			if rv == 0 && !body.is_empty() {
+				rv = nng_sys::nng_http_res_copy_data(res, body.as_ptr() as _, body.len());
+			}
+
+			if rv == 0 {
+				nng_sys::nng_aio_set_output(aio, 0, res as _);
+			}
+			else if !res.is_null() {
+				nng_sys::nng_http_res_free(res);
+			}
+
+			nng_sys::nng_aio_finish(aio, rv);
+		});
+	}
+
+	/// Drops the handler closure once NNG has freed the handler.
+	///
+	/// This is unsafe because the pointer must have come from `Box::into_raw`
+	/// on a `Box<HandlerCallback>`.
+	unsafe extern "C" fn drop_callback(arg: *mut c_void)
+	{
+		abort_unwind(|| drop(Box::from_raw(arg as *mut HandlerCallback)));
+	}
+}
+
+impl fmt::Debug for HttpServer
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		f.debug_struct("HttpServer").field("server", &self.server).finish()
+	}
+}
+
+impl Drop for HttpServer
+{
+	fn drop(&mut self)
+	{
+		// This only decrements the reference count.
+		unsafe { nng_sys::nng_http_server_release(self.server.as_ptr()) }
+	}
+}
+
+// NNG protects the server with its own lock and the handlers are required to
+// be `Send` and `Sync`.
+unsafe impl Send for HttpServer {}
+unsafe impl Sync for HttpServer {}
+
+/// A request received by an [`HttpServer`] handler.
+///
+/// [`HttpServer`]: struct.HttpServer.html
+pub struct HttpRequest<'a>
+{
+	/// The underlying NNG HTTP request.
+	req: NonNull<nng_sys::nng_http_req>,
+
+	/// The request is only valid for the duration of the handler call.
+	_phantom: PhantomData<&'a ()>,
+}
+impl<'a> HttpRequest<'a>
+{
+	/// Returns the method of the request, such as `GET`.
+	pub fn method(&self) -> &str
+	{
+		unsafe { to_str(nng_sys::nng_http_req_get_method(self.req.as_ptr())) }.unwrap_or("")
+	}
+
+	/// Returns the URI of the request, including the path and query.
+	pub fn uri(&self) -> &str
+	{
+		unsafe { to_str(nng_sys::nng_http_req_get_uri(self.req.as_ptr())) }.unwrap_or("")
+	}
+
+	/// Returns the value of the named header, if the client sent it.
+	///
+	/// Header names are compared case-insensitively.
+	pub fn header(&self, name: &str) -> Option<&str>
+	{
+		let name = CString::new(name).ok()?;
+		unsafe { to_str(nng_sys::nng_http_req_get_header(self.req.as_ptr(), name.as_ptr())) }
+	}
+
+	/// Returns the body of the request.
+	pub fn body(&self) -> &[u8]
+	{
+		let mut data: *mut c_void = ptr::null_mut();
+		let mut size = 0;
+		unsafe {
+			nng_sys::nng_http_req_get_data(self.req.as_ptr(), &mut data as *mut _, &mut size as *mut _);
+			if data.is_null() { &[] } else { slice::from_raw_parts(data as *const u8, size) }
+		}
+	}
+}
+
+impl<'a> fmt::Debug for HttpRequest<'a>
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		f.debug_struct("HttpRequest")
+			.field("method", &self.method())
+			.field("uri", &self.uri())
+			.field("body_len", &self.body().len())
+			.finish()
+	}
+}
+
+/// Converts a string owned by NNG into a Rust string, if it is present.
+///
+/// This is unsafe because the pointer must be null or point to a valid C string
+/// that outlives the returned reference.
+unsafe fn to_str<'a>(ptr: *const c_char) -> Option<&'a str>
+{
+	if ptr.is_null() {
+		None
+	}
+	else {
+		CStr::from_ptr(ptr).to_str().ok()
+	}
+}
@@ -35,6 +35,12 @@
 //!   library but not this wrapper. Note that this exposes some internal items
 //!   of this library and it directly exposes the NNG library, so anything
 //!   enabled by this can change without bumping versions.
+//! * `serde`: Add functions for sending and receiving values serialized with
+//!   Serde, along with the `Codec` trait and the `Bincode` codec.
+//! * `mio`: Implement `mio::event::Source` for `Socket` on Unix platforms,
+//!   allowing sockets to be registered with a `mio` reactor.
+//! * `test-util`: Expose the `testutil` module, which contains helpers for
+//!   writing tests, such as connected socket pairs on unique addresses.
 //!
 //! ### Building NNG
 //!
@@ -160,35 +166,73 @@ mod util;
 
 mod addr;
 mod aio;
+#[cfg(feature = "serde")]
+mod codec;
 mod ctx;
 mod device;
 mod dialer;
 mod error;
+mod future;
+mod http;
 mod listener;
 mod message;
 mod pipe;
+mod pool;
 mod protocol;
 mod socket;
+mod stats;
+mod stream;
+mod tls;
+mod url;
 
 pub mod options;
+#[cfg(feature = "test-util")]
+pub mod testutil;
 
 pub use crate::{
 	addr::SocketAddr,
 	aio::{Aio, AioResult},
-	ctx::Context,
+	ctx::{Context, ContextPool},
 	device::{forwarder, reflector},
 	dialer::{Dialer, DialerBuilder},
 	error::{Error, Result},
+	future::{RecvFuture, SendFuture},
+	http::{HttpClient, HttpRequest, HttpResponse, HttpServer},
 	listener::{Listener, ListenerBuilder},
-	message::{Header, Message},
+	message::{ByteArray, Header, Message, MessageReader},
 	pipe::{Pipe, PipeEvent},
+	pool::{MessagePool, PooledMessage},
 	protocol::Protocol,
-	socket::{RawSocket, Socket},
+	socket::{Incoming, RawSocket, Socket, SocketBuilder},
+	stats::{stats, Stat, StatChildren, StatValue, Stats},
+	stream::SocketStream,
+	tls::{TlsAuthMode, TlsConfig, TlsMode},
+	url::Url,
 };
 
+#[cfg(feature = "serde")]
+pub use crate::codec::{Bincode, Codec, CodecError};
+
 #[cfg(feature = "ffi-module")]
 /// Raw NNG foreign function interface.
 pub use nng_sys as ffi;
 
 #[cfg(feature = "ffi-module")]
 pub use crate::aio::State as AioState;
+
+/// Returns the version of the NNG library in use.
+///
+/// The version is of the form `major.minor.patch` and refers to the linked
+/// NNG library, not this crate.
+///
+/// ```
+/// let version = nng::version();
+/// assert_eq!(version.split('.').count(), 3);
+/// ```
+pub fn version() -> &'static str
+{
+	// NNG returns a pointer to a static, null-terminated ASCII string.
+	unsafe { std::ffi::CStr::from_ptr(nng_sys::nng_version()) }
+		.to_str()
+		.expect("NNG version string is not ASCII")
+}
@@ -7,7 +7,9 @@ use std::{
 
 use crate::{
 	error::{Error, Result},
+	options::{self, transport::tcp::BoundPort, Options},
 	socket::Socket,
+	url::Url,
 };
 
 /// Active listener for incoming connections.
@@ -47,14 +49,16 @@ impl Listener
 	/// # Errors
 	///
 	/// * [`AddressInUse`]: The address specified by _url_ is already in use.
-	/// * [`Addressinvalid`]: An invalid _url_ was specified.
+	/// * [`AddressInvalid`]: An invalid _url_ was specified.
 	/// * [`Closed`]: The socket is not open.
+	/// * [`IncorrectState`]: The socket requires a receive size limit and none is set.
 	/// * [`OutOfMemory`]: Insufficient memory is available.
 	///
 	///
 	/// [`AddressInUse`]: enum.Error.html#variant.AddressInUse
-	/// [`Addressinvalid`]: enum.Error.html#variant.Addressinvalid
+	/// [`AddressInvalid`]: enum.Error.html#variant.AddressInvalid
 	/// [`Closed`]: enum.Error.html#variant.Closed
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
 	/// [`ListenerBuilder`]: struct.ListenerBuilder.html
 	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
 	pub fn new(socket: &Socket, url: &str) -> Result<Self>
@@ -63,6 +67,7 @@ impl Listener
 		// creating the listener will far outweigh the cost of allocating a
 		// single string. Having a full Rust interface will make it easier to
 		// work with.
+		socket.check_recv_limit()?;
 		let addr = CString::new(url).map_err(|_| Error::AddressInvalid)?;
 		let mut handle = nng_sys::nng_listener::NNG_LISTENER_INITIALIZER;
 
@@ -98,6 +103,67 @@ impl Listener
 		);
 	}
 
+	/// Returns the address the listener is bound to.
+	///
+	/// This is the URL the listener was created with, except that a wildcard
+	/// (0) TCP or TLS port is replaced with the [port that was actually
+	/// bound][1]. All other components, including any user information, query
+	/// and fragment, are kept as they were. The result can be handed to peers
+	/// so that they can dial the listener.
+	///
+	/// # Errors
+	///
+	/// * [`Closed`]: The listener is closed.
+	/// * [`AddressInvalid`]: The configured URL could not be parsed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Listener, Protocol, Socket};
+	///
+	/// let server = Socket::new(Protocol::Rep0).unwrap();
+	/// let listener = Listener::new(&server, "tcp://127.0.0.1:0").unwrap();
+	///
+	/// let url = listener.local_url().unwrap();
+	/// assert_ne!(url.port(), Some(0));
+	///
+	/// let client = Socket::new(Protocol::Req0).unwrap();
+	/// client.dial(url.as_str()).unwrap();
+	/// ```
+	///
+	/// [1]: options/transport/tcp/enum.BoundPort.html
+	/// [`AddressInvalid`]: enum.Error.html#variant.AddressInvalid
+	/// [`Closed`]: enum.Error.html#variant.Closed
+	pub fn local_url(self) -> Result<Url>
+	{
+		let url = Url::parse(&self.get_opt::<options::Url>()?)?;
+		if url.port() != Some(0) {
+			return Ok(url);
+		}
+
+		let port = self.get_opt::<BoundPort>()?;
+		let host = url.hostname();
+		let host = if host.contains(':') { format!("[{}]", host) } else { host.to_string() };
+
+		// Rebuild the URL with every component of the original, only swapping the port.
+		let mut raw = format!("{}://", url.scheme());
+		if let Some(userinfo) = url.userinfo() {
+			raw.push_str(userinfo);
+			raw.push('@');
+		}
+		raw.push_str(&format!("{}:{}{}", host, port, url.path()));
+		if let Some(query) = url.query() {
+			raw.push('?');
+			raw.push_str(query);
+		}
+		if let Some(fragment) = url.fragment() {
+			raw.push('#');
+			raw.push_str(fragment);
+		}
+
+		Url::parse(&raw)
+	}
+
 	/// Create a new `Listener` handle from a NNG handle.
 	///
 	/// This function will panic if the handle is not valid.
@@ -175,7 +241,8 @@ expose_options!{
 	SETOPT_STRING = nng_sys::nng_listener_set_string;
 
 	Gets -> [LocalAddr, Raw, RecvBufferSize,
-	         RecvTimeout, SendBufferSize, Url,
+	         RecvMaxSize, RecvTimeout,
+	         SendBufferSize, Url,
 	         SendTimeout, SocketName, MaxTtl,
 	         protocol::reqrep::ResendTime,
 	         protocol::survey::SurveyTime,
@@ -193,6 +260,24 @@ expose_options!{
 /// listener without closing the socket, then [`Socket::listen`] provides a
 /// simpler interface.
 ///
+/// ## Example
+///
+/// ```
+/// use nng::{
+///     options::{Options, RecvMaxSize},
+///     ListenerBuilder, Protocol, Socket,
+/// };
+///
+/// let socket = Socket::new(Protocol::Rep0).unwrap();
+/// let builder = ListenerBuilder::new(&socket, "tcp://127.0.0.1:0").unwrap();
+///
+/// // The limit is in place before any peer is able to connect.
+/// builder.set_opt::<RecvMaxSize>(4096).unwrap();
+///
+/// let listener = builder.start().map_err(|(_, e)| e).unwrap();
+/// listener.close();
+/// ```
+///
 ///
 /// [`Socket::listen`]: struct.Socket.html#method.listen
 #[derive(Debug)]
@@ -200,6 +285,9 @@ pub struct ListenerBuilder
 {
 	/// The underlying listener object that we are configuring
 	handle: nng_sys::nng_listener,
+
+	/// Whether the socket required a receive size limit when this was created.
+	require_recv_limit: bool,
 }
 impl ListenerBuilder
 {
@@ -232,7 +320,7 @@ impl ListenerBuilder
 			nng_sys::nng_listener_create(&mut handle as *mut _, socket.handle(), addr.as_ptr())
 		};
 
-		rv2res!(rv, ListenerBuilder { handle })
+		rv2res!(rv, ListenerBuilder { handle, require_recv_limit: socket.recv_limit_required() })
 	}
 
 	/// Cause the [`Listener`] to start listening on the address with which it was
@@ -245,12 +333,23 @@ impl ListenerBuilder
 	/// # Errors
 	///
 	/// * [`Closed`]: The socket is not open.
+	/// * [`IncorrectState`]: The socket requires a receive size limit and none is set.
 	///
 	///
 	/// [`Closed`]: enum.Error.html#variant.Closed
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
 	/// [`Listener`]: struct.Listener.html
 	pub fn start(self) -> std::result::Result<Listener, (Self, Error)>
 	{
+		// The listener inherits the limit of the socket unless it was given its own.
+		if self.require_recv_limit {
+			match self.get_opt::<options::RecvMaxSize>() {
+				Ok(0) => return Err((self, Error::IncorrectState)),
+				Err(e) => return Err((self, e)),
+				Ok(_) => {},
+			}
+		}
+
 		// If there is an error starting the listener, we don't want to consume
 		// it. Instead, we'll return it to the user and they can decide what to
 		// do.
@@ -295,7 +394,8 @@ expose_options!{
 	SETOPT_STRING = nng_sys::nng_listener_setopt_string;
 
 	Gets -> [LocalAddr, Raw, RecvBufferSize,
-	         RecvTimeout, SendBufferSize, Url,
+	         RecvMaxSize, RecvTimeout,
+	         SendBufferSize, Url,
 	         SendTimeout, SocketName, MaxTtl,
 	         protocol::reqrep::ResendTime,
 	         protocol::survey::SurveyTime,
@@ -306,6 +406,7 @@ expose_options!{
 	         transport::tcp::KeepAlive,
 	         transport::tls::CaFile,
 	         transport::tls::CertKeyFile,
+	         transport::tls::Config,
 	         transport::websocket::ResponseHeaders,
 	         transport::websocket::Protocol];
 }
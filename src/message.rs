@@ -1,9 +1,12 @@
 use std::{
+	fmt,
+	hash::{Hash, Hasher},
 	io::{self, Write},
 	iter::FromIterator,
 	ops::{Deref, DerefMut, Index, IndexMut},
 	ptr::{self, NonNull},
 	slice::{self, SliceIndex},
+	str,
 };
 
 use crate::{pipe::Pipe, util::validate_ptr};
@@ -20,12 +23,21 @@ const ALLOC_FAIL_MSG: &str = "NNG failed to allocate memory";
 /// In addition to the regular portion of the message there is a header that
 /// carries protocol specific header information. Most applications will not
 /// need to touch the header and will only interact with the regular message.
+///
+/// Two messages compare equal (and hash identically) when their bodies
+/// contain the same bytes. The header and the associated pipe do not take
+/// part in the comparison, as they are protocol routing information rather
+/// than content; compare [`as_header`] separately when it matters.
+///
+/// The `Debug` output shows the length of the body and a hex and ASCII dump of
+/// its first 64 bytes, so logging a large message stays readable.
+///
+/// [`as_header`]: #method.as_header
 // None of these methods will report failure. As of this writing (NNG v1.2.3) the only possible
 // error condition in any of the message functions is if allocation, which is a regular `malloc` or
 // `calloc` call), fails. Both of the platforms this crate really supports do overcommit, which
 // means those will never fail. Also, Rust normally panics on allocation failure, so this fits the
 // ecosystem better.
-#[derive(Debug)]
 pub struct Message
 {
 	/// The pointer to the actual message.
@@ -56,8 +68,13 @@ impl Message
 
 	/// Create an empty message with a pre-allocated body buffer.
 	///
-	/// The returned buffer will have a capacity equal to `cap` but a length of
-	/// zero. To get a `Message` with a specified length, use `Message::zeros`.
+	/// The returned buffer will have a capacity of at least `cap` but a length
+	/// of zero. To get a `Message` with a specified length, use
+	/// `Message::with_zeros`.
+	///
+	/// The capacity is a hint: NNG guarantees that the body can grow to `cap`
+	/// bytes without reallocating, but it may reserve more and it does not
+	/// provide a way to query the actual capacity.
 	pub fn with_capacity(cap: usize) -> Self
 	{
 		let mut msgp: *mut nng_sys::nng_msg = ptr::null_mut();
@@ -83,6 +100,25 @@ impl Message
 		Message::from_ptr(msgp)
 	}
 
+	/// Reserves capacity for at least `additional` more bytes in the body.
+	///
+	/// After calling this, appending up to `additional` bytes to the message
+	/// body will not cause a reallocation. As with `Message::with_capacity`,
+	/// NNG may reserve more space than requested.
+	pub fn reserve(&mut self, additional: usize)
+	{
+		// Growing the message reallocates the body if needed, after which the new
+		// bytes can be chopped off again. NNG does not give the space back.
+		let rv = unsafe {
+			let len = nng_sys::nng_msg_len(self.msgp.as_ptr());
+			nng_sys::nng_msg_realloc(self.msgp.as_ptr(), len.saturating_add(additional))
+		};
+		rv2res!(rv).expect(ALLOC_FAIL_MSG);
+
+		let rv = unsafe { nng_sys::nng_msg_chop(self.msgp.as_ptr(), additional) };
+		debug_assert_eq!(rv, 0, "Message was too short to chop");
+	}
+
 	/// Shortens the message, dropping excess elements from the back.
 	///
 	/// If `len` is greater than the message body's current length, this has no
@@ -112,6 +148,105 @@ impl Message
 		debug_assert_eq!(rv, 0, "Message was too short to trim");
 	}
 
+	/// Remove the last `len` bytes from the back of the message body.
+	///
+	/// If `len` is greater than the message body's current length then this
+	/// will clear the entire message.
+	pub fn chop(&mut self, len: usize)
+	{
+		let rv = unsafe {
+			let current_len = nng_sys::nng_msg_len(self.msgp.as_ptr());
+			nng_sys::nng_msg_chop(self.msgp.as_ptr(), len.min(current_len))
+		};
+
+		debug_assert_eq!(rv, 0, "Message was too short to chop");
+	}
+
+	/// Removes a big-endian `u16` from the front of the message body.
+	///
+	/// If the message body is too short, `None` is returned and the message is
+	/// left unchanged.
+	pub fn trim_u16_be(&mut self) -> Option<u16>
+	{
+		let mut val = 0;
+		let rv = unsafe { nng_sys::nng_msg_trim_u16(self.msgp.as_ptr(), &mut val as _) };
+
+		if rv == 0 { Some(val) } else { None }
+	}
+
+	/// Removes a big-endian `u32` from the front of the message body.
+	///
+	/// If the message body is too short, `None` is returned and the message is
+	/// left unchanged.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::Message;
+	///
+	/// let mut msg = Message::from(&[0, 0, 0, 5, b'h', b'e', b'l', b'l', b'o'][..]);
+	/// assert_eq!(msg.trim_u32_be(), Some(5));
+	/// assert_eq!(&msg[..], b"hello");
+	///
+	/// msg.clear();
+	/// assert_eq!(msg.trim_u32_be(), None);
+	/// ```
+	pub fn trim_u32_be(&mut self) -> Option<u32>
+	{
+		let mut val = 0;
+		let rv = unsafe { nng_sys::nng_msg_trim_u32(self.msgp.as_ptr(), &mut val as _) };
+
+		if rv == 0 { Some(val) } else { None }
+	}
+
+	/// Removes a big-endian `u64` from the front of the message body.
+	///
+	/// If the message body is too short, `None` is returned and the message is
+	/// left unchanged.
+	pub fn trim_u64_be(&mut self) -> Option<u64>
+	{
+		let mut val = 0;
+		let rv = unsafe { nng_sys::nng_msg_trim_u64(self.msgp.as_ptr(), &mut val as _) };
+
+		if rv == 0 { Some(val) } else { None }
+	}
+
+	/// Removes a big-endian `u16` from the back of the message body.
+	///
+	/// If the message body is too short, `None` is returned and the message is
+	/// left unchanged.
+	pub fn chop_u16_be(&mut self) -> Option<u16>
+	{
+		let mut val = 0;
+		let rv = unsafe { nng_sys::nng_msg_chop_u16(self.msgp.as_ptr(), &mut val as _) };
+
+		if rv == 0 { Some(val) } else { None }
+	}
+
+	/// Removes a big-endian `u32` from the back of the message body.
+	///
+	/// If the message body is too short, `None` is returned and the message is
+	/// left unchanged.
+	pub fn chop_u32_be(&mut self) -> Option<u32>
+	{
+		let mut val = 0;
+		let rv = unsafe { nng_sys::nng_msg_chop_u32(self.msgp.as_ptr(), &mut val as _) };
+
+		if rv == 0 { Some(val) } else { None }
+	}
+
+	/// Removes a big-endian `u64` from the back of the message body.
+	///
+	/// If the message body is too short, `None` is returned and the message is
+	/// left unchanged.
+	pub fn chop_u64_be(&mut self) -> Option<u64>
+	{
+		let mut val = 0;
+		let rv = unsafe { nng_sys::nng_msg_chop_u64(self.msgp.as_ptr(), &mut val as _) };
+
+		if rv == 0 { Some(val) } else { None }
+	}
+
 	/// Returns a slice that contains the contents of the message body.
 	pub fn as_slice(&self) -> &[u8]
 	{
@@ -134,12 +269,40 @@ impl Message
 		}
 	}
 
+	/// Returns the message body as a string slice.
+	///
+	/// # Errors
+	///
+	/// Returns a `Utf8Error` if the body is not valid UTF-8.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::Message;
+	///
+	/// let msg = Message::from("hello");
+	/// assert_eq!(msg.as_str(), Ok("hello"));
+	/// ```
+	pub fn as_str(&self) -> Result<&str, str::Utf8Error> { str::from_utf8(self.as_slice()) }
+
 	/// Returns a reference to the message header.
 	pub const fn as_header(&self) -> &Header { &self.header }
 
 	/// Returns a mutable reference to the message header.
 	pub fn as_mut_header(&mut self) -> &mut Header { &mut self.header }
 
+	/// Replaces the header of this message with a copy of the other's header.
+	///
+	/// This is primarily useful for raw sockets, where a reply must carry the
+	/// header of the request it answers so that it can be routed back to the
+	/// requester. Only the header is copied; the body and pipe of this message
+	/// are left unchanged.
+	pub fn copy_header_from(&mut self, other: &Message)
+	{
+		self.header.clear();
+		self.header.push_back(other.as_header());
+	}
+
 	/// Returns the length of the message.
 	pub fn len(&self) -> usize { unsafe { nng_sys::nng_msg_len(self.msgp.as_ptr()) } }
 
@@ -147,6 +310,15 @@ impl Message
 	pub fn is_empty(&self) -> bool { self.len() == 0 }
 
 	/// Clears the message body.
+	///
+	/// Only the body is emptied. The header and the associated pipe are left
+	/// untouched; use [`Header::clear`] to empty the header as well. The memory
+	/// allocated for the body is kept, so the message can be reused without
+	/// reallocating. To shorten the body to a specific length instead, use
+	/// [`truncate`].
+	///
+	/// [`Header::clear`]: struct.Header.html#method.clear
+	/// [`truncate`]: #method.truncate
 	pub fn clear(&mut self)
 	{
 		unsafe {
@@ -172,6 +344,39 @@ impl Message
 		rv2res!(rv).expect(ALLOC_FAIL_MSG)
 	}
 
+	/// Appends a single byte to the back of the message body.
+	pub fn push_u8(&mut self, val: u8) { self.push_back(&[val]) }
+
+	/// Appends a big-endian `u16` to the back of the message body.
+	pub fn push_u16_be(&mut self, val: u16) { self.push_back(&val.to_be_bytes()) }
+
+	/// Appends a little-endian `u16` to the back of the message body.
+	pub fn push_u16_le(&mut self, val: u16) { self.push_back(&val.to_le_bytes()) }
+
+	/// Appends a big-endian `u32` to the back of the message body.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::Message;
+	///
+	/// let mut msg = Message::new();
+	/// msg.push_u8(1);
+	/// msg.push_u32_be(0x0203_0405);
+	/// msg.push_u16_le(0x0706);
+	/// assert_eq!(&msg[..], &[1, 2, 3, 4, 5, 6, 7]);
+	/// ```
+	pub fn push_u32_be(&mut self, val: u32) { self.push_back(&val.to_be_bytes()) }
+
+	/// Appends a little-endian `u32` to the back of the message body.
+	pub fn push_u32_le(&mut self, val: u32) { self.push_back(&val.to_le_bytes()) }
+
+	/// Appends a big-endian `u64` to the back of the message body.
+	pub fn push_u64_be(&mut self, val: u64) { self.push_back(&val.to_be_bytes()) }
+
+	/// Appends a little-endian `u64` to the back of the message body.
+	pub fn push_u64_le(&mut self, val: u64) { self.push_back(&val.to_le_bytes()) }
+
 	/// Returns the pipe object associated with the message.
 	///
 	/// On receive, this is the pipe from which the message was received. On
@@ -183,7 +388,7 @@ impl Message
 	/// from which the message was received. This can be used to provide
 	/// different behaviors for different peers, such as a higher level of
 	/// authentication for peers located on an untrusted network.
-	pub fn pipe(&mut self) -> Option<Pipe>
+	pub fn pipe(&self) -> Option<Pipe>
 	{
 		let (pipe, id) = unsafe {
 			let pipe = nng_sys::nng_msg_get_pipe(self.msgp.as_ptr());
@@ -204,6 +409,19 @@ impl Message
 		unsafe { nng_sys::nng_msg_set_pipe(self.msgp.as_ptr(), pipe.handle()) }
 	}
 
+	/// Clears the body, header, and pipe of the message so that it can be reused.
+	pub(crate) fn reset(&mut self)
+	{
+		self.clear();
+		self.header.clear();
+		unsafe {
+			nng_sys::nng_msg_set_pipe(
+				self.msgp.as_ptr(),
+				nng_sys::nng_pipe::NNG_PIPE_INITIALIZER,
+			)
+		}
+	}
+
 	/// Creates a new message from the given pointer.
 	pub(crate) const fn from_ptr(msgp: NonNull<nng_sys::nng_msg>) -> Self
 	{
@@ -252,6 +470,29 @@ impl Clone for Message
 	}
 }
 
+impl PartialEq for Message
+{
+	fn eq(&self, other: &Message) -> bool { self.as_slice() == other.as_slice() }
+}
+
+impl Eq for Message {}
+
+impl Hash for Message
+{
+	fn hash<H: Hasher>(&self, state: &mut H) { self.as_slice().hash(state) }
+}
+
+impl fmt::Debug for Message
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		f.debug_struct("Message")
+			.field("len", &self.len())
+			.field("body", &HexDump(self.as_slice()))
+			.finish()
+	}
+}
+
 impl Default for Message
 {
 	fn default() -> Message { Message::new() }
@@ -281,6 +522,26 @@ impl<'a> From<&'a Vec<u8>> for Message
 	fn from(s: &Vec<u8>) -> Message { s.as_slice().into() }
 }
 
+impl From<Vec<u8>> for Message
+{
+	fn from(s: Vec<u8>) -> Message { s.as_slice().into() }
+}
+
+impl<'a> From<&'a str> for Message
+{
+	fn from(s: &str) -> Message { s.as_bytes().into() }
+}
+
+impl<'a> From<&'a String> for Message
+{
+	fn from(s: &String) -> Message { s.as_bytes().into() }
+}
+
+impl From<String> for Message
+{
+	fn from(s: String) -> Message { s.as_bytes().into() }
+}
+
 macro_rules! array_impl
 {
 	($s:tt) => {
@@ -291,6 +552,9 @@ macro_rules! array_impl
 				s[..].into()
 			}
 		}
+
+		impl private::Sealed for [u8; $s] {}
+		impl ByteArray for [u8; $s] {}
 	};
 
 	($s:tt, $($r:tt),+) => {
@@ -402,6 +666,24 @@ impl<I: SliceIndex<[u8]>> IndexMut<I> for Message
 /// Most normal applications will never have to touch the message header. The
 /// only time it will be necessary is if the socket is in "raw" mode ([`RawSocket`]).
 ///
+/// ## Example
+///
+/// A raw _rep_ socket must copy the request's header, which contains the
+/// routing information and request ID, into the reply:
+///
+/// ```
+/// use nng::Message;
+///
+/// # let mut request = Message::new();
+/// # request.as_mut_header().push_back(&[0x80, 0, 0, 1]);
+/// // `request` was received from a raw Rep0 socket.
+/// let mut reply = Message::from(&b"reply"[..]);
+/// reply.copy_header_from(&request);
+///
+/// assert_eq!(reply.as_header().as_slice(), request.as_header().as_slice());
+/// assert_eq!(reply.as_header().len(), 4);
+/// ```
+///
 ///
 /// [`Message`]: struct.Message.html
 /// [`RawSocket`]: struct.RawSocket.html
@@ -565,3 +847,212 @@ impl<I: SliceIndex<[u8]>> IndexMut<I> for Header
 	#[inline]
 	fn index_mut(&mut self, index: I) -> &mut Self::Output { self.as_mut_slice().index_mut(index) }
 }
+
+/// A cursor for reading the body of a [`Message`] without modifying it.
+///
+/// The reader keeps track of its position within the body and hands out
+/// integers and slices sequentially. If there are not enough bytes left for a
+/// read, `None` is returned and the position is left unchanged. Unlike the
+/// [`trim`] family of functions, the message itself is never modified, so it
+/// can be read again or kept as is after parsing.
+///
+/// The byte order of each integer is part of the function name, `_be` for
+/// big-endian (network) order and `_le` for little-endian order, matching the
+/// [`trim_u32_be`] and [`push_u32_be`] families.
+///
+/// ## Example
+///
+/// ```
+/// use nng::{Message, MessageReader};
+///
+/// let msg = Message::from(&[0, 5, b'h', b'e', b'l', b'l', b'o', 1][..]);
+/// let mut reader = MessageReader::new(&msg);
+///
+/// let len = reader.read_u16_be().unwrap();
+/// assert_eq!(reader.read_bytes(len as usize), Some(&b"hello"[..]));
+/// assert_eq!(reader.read_u32_be(), None);
+/// assert_eq!(reader.read_u8(), Some(1));
+/// assert!(reader.is_empty());
+///
+/// // The message is untouched.
+/// assert_eq!(msg.len(), 8);
+/// ```
+///
+/// [`Message`]: struct.Message.html
+/// [`trim`]: struct.Message.html#method.trim
+/// [`push_u32_be`]: struct.Message.html#method.push_u32_be
+/// [`trim_u32_be`]: struct.Message.html#method.trim_u32_be
+#[derive(Clone, Debug)]
+pub struct MessageReader<'a>
+{
+	/// The body of the message.
+	body: &'a [u8],
+
+	/// The offset of the next byte to read.
+	pos: usize,
+}
+impl<'a> MessageReader<'a>
+{
+	/// Creates a new reader positioned at the start of the message body.
+	pub fn new(msg: &'a Message) -> Self { MessageReader { body: msg.as_slice(), pos: 0 } }
+
+	/// Returns the offset of the next byte to be read.
+	pub const fn position(&self) -> usize { self.pos }
+
+	/// Moves the reader to the specified offset.
+	///
+	/// Returns `None` without moving if the offset is past the end of the body.
+	pub fn seek(&mut self, pos: usize) -> Option<()>
+	{
+		if pos > self.body.len() {
+			return None;
+		}
+
+		self.pos = pos;
+		Some(())
+	}
+
+	/// Returns the bytes that have not been read yet.
+	pub fn remaining(&self) -> &'a [u8] { &self.body[self.pos..] }
+
+	/// Returns the number of bytes that have not been read yet.
+	pub fn len(&self) -> usize { self.body.len() - self.pos }
+
+	/// Returns true if all of the bytes have been read.
+	pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+	/// Reads the next `len` bytes.
+	pub fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]>
+	{
+		let bytes = self.remaining().get(..len)?;
+		self.pos += len;
+		Some(bytes)
+	}
+
+	/// Reads a single byte.
+	pub fn read_u8(&mut self) -> Option<u8> { self.read_bytes(1).map(|b| b[0]) }
+
+	/// Reads a big-endian `u16`.
+	pub fn read_u16_be(&mut self) -> Option<u16> { self.read_array().map(u16::from_be_bytes) }
+
+	/// Reads a big-endian `u32`.
+	pub fn read_u32_be(&mut self) -> Option<u32> { self.read_array().map(u32::from_be_bytes) }
+
+	/// Reads a big-endian `u64`.
+	pub fn read_u64_be(&mut self) -> Option<u64> { self.read_array().map(u64::from_be_bytes) }
+
+	/// Reads a little-endian `u16`.
+	pub fn read_u16_le(&mut self) -> Option<u16> { self.read_array().map(u16::from_le_bytes) }
+
+	/// Reads a little-endian `u32`.
+	pub fn read_u32_le(&mut self) -> Option<u32> { self.read_array().map(u32::from_le_bytes) }
+
+	/// Reads a little-endian `u64`.
+	pub fn read_u64_le(&mut self) -> Option<u64> { self.read_array().map(u64::from_le_bytes) }
+
+	/// Reads the next bytes into a fixed-size array.
+	///
+	/// The length of the array determines how many bytes are read. This works
+	/// for any byte array up to 32 bytes long, which makes it convenient for
+	/// fixed-layout fields such as identifiers or hashes. See [`ByteArray`]
+	/// for the supported types.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Message, MessageReader};
+	///
+	/// let msg = Message::from(&[1, 2, 3, 4, 5, 6][..]);
+	/// let mut reader = MessageReader::new(&msg);
+	///
+	/// let magic: [u8; 4] = reader.read_array().unwrap();
+	/// assert_eq!(magic, [1, 2, 3, 4]);
+	/// assert_eq!(reader.read_array::<[u8; 4]>(), None);
+	/// assert_eq!(reader.remaining(), &[5, 6]);
+	/// ```
+	///
+	/// [`ByteArray`]: trait.ByteArray.html
+	pub fn read_array<A: ByteArray>(&mut self) -> Option<A>
+	{
+		let mut array = A::default();
+		self.read_into(array.as_mut())?;
+
+		Some(array)
+	}
+
+	/// Fills the buffer with the next bytes.
+	///
+	/// Returns `None` without reading anything if fewer than `buf.len()` bytes
+	/// remain.
+	pub fn read_into(&mut self, buf: &mut [u8]) -> Option<()>
+	{
+		let bytes = self.read_bytes(buf.len())?;
+		buf.copy_from_slice(bytes);
+
+		Some(())
+	}
+}
+
+impl<'a> io::Read for MessageReader<'a>
+{
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>
+	{
+		let len = buf.len().min(self.len());
+		buf[..len].copy_from_slice(&self.remaining()[..len]);
+		self.pos += len;
+
+		Ok(len)
+	}
+}
+
+/// The maximum number of bytes shown by the `Debug` implementation of `Message`.
+const DEBUG_DUMP_LIMIT: usize = 64;
+
+/// Formats a byte slice as a bounded hex and ASCII dump.
+struct HexDump<'a>(&'a [u8]);
+impl<'a> fmt::Debug for HexDump<'a>
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		let shown = &self.0[..self.0.len().min(DEBUG_DUMP_LIMIT)];
+		let ellipsis = if shown.len() < self.0.len() { "..." } else { "" };
+
+		f.write_str("\"")?;
+		for (i, b) in shown.iter().enumerate() {
+			if i > 0 {
+				f.write_str(" ")?;
+			}
+			write!(f, "{:02x}", b)?;
+		}
+		write!(f, "{} |", ellipsis)?;
+		for &b in shown {
+			let c = if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' };
+			write!(f, "{}", c)?;
+		}
+		write!(f, "|{}\"", ellipsis)
+	}
+}
+
+/// A fixed-size byte array that can be read with [`MessageReader::read_array`].
+///
+/// This trait is sealed and is implemented for `[u8; N]` with `N` up to 32. It
+/// exists so that types such as `Vec<u8>`, whose default value is empty, cannot
+/// be used to silently read zero bytes.
+///
+/// ```compile_fail
+/// use nng::{Message, MessageReader};
+///
+/// let msg = Message::from(&[1, 2, 3, 4][..]);
+/// let mut reader = MessageReader::new(&msg);
+/// let bytes: Option<Vec<u8>> = reader.read_array(); // Won't compile
+/// ```
+///
+/// [`MessageReader::read_array`]: struct.MessageReader.html#method.read_array
+pub trait ByteArray: private::Sealed + Default + AsMut<[u8]> {}
+
+/// Module used to prevent `ByteArray` from being implemented outside of this crate.
+mod private
+{
+	/// Marker trait that seals `ByteArray`.
+	pub trait Sealed {}
+}
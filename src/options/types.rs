@@ -3,7 +3,19 @@ use std::time::Duration;
 #[cfg(unix)]
 use std::os::unix::io::RawFd;
 
-use crate::addr::SocketAddr;
+use crate::{
+	addr::SocketAddr,
+	error::{Error, Result},
+};
+
+/// The largest buffer depth, in messages, that NNG accepts.
+const MAX_BUFFER_DEPTH: i32 = 8192;
+
+/// Checks that a socket buffer depth is within the range NNG accepts.
+fn buffer_depth(val: i32) -> Result<i32>
+{
+	if (0..=MAX_BUFFER_DEPTH).contains(&val) { Ok(val) } else { Err(Error::InvalidInput) }
+}
 
 create_option! {
 	/// The local address used for communication.
@@ -93,6 +105,9 @@ create_option! {
 	/// attempt will wait the time specified by `ReconnectMinTime`. This can be
 	/// set on a socket, but it can also be overridden on an individual dialer.
 	///
+	/// The back-off is reset once a connection is established, so the first
+	/// attempt after that connection is lost again waits only the minimum time.
+	///
 	/// ## Support
 	///
 	/// * Dialers can use this option.
@@ -106,7 +121,11 @@ create_option! {
 	/// The depth of the socket's receive buffer as a number of messages.
 	///
 	/// Messages received by the transport may be buffered until the
-	/// application has accepted them for delivery.
+	/// application has accepted them for delivery. This value must be an
+	/// integer between 0 and 8192, inclusive. Other values are rejected with
+	/// [`InvalidInput`] before reaching NNG.
+	///
+	/// [`InvalidInput`]: ../enum.Error.html#variant.InvalidInput
 	///
 	/// ## Support
 	///
@@ -114,7 +133,7 @@ create_option! {
 	/// * Dialers and Listeners can retrieve it from their owning Socket.
 	RecvBufferSize -> i32:
 	Get s = s.getopt_int(nng_sys::NNG_OPT_RECVBUF as *const _ as _);
-	Set s val = s.setopt_int(nng_sys::NNG_OPT_RECVBUF as *const _ as _, val);
+	Set s val = s.setopt_int(nng_sys::NNG_OPT_RECVBUF as *const _ as _, buffer_depth(val)?);
 }
 
 #[cfg(unix)]
@@ -128,8 +147,9 @@ create_option! {
 	/// While this may be useful for integrating into existing polling loops, the
 	/// use of asynchronous I/O objects will be more efficient.
 	///
-	/// Applications should **never** attempt to read or write to the file
-	/// descriptor.
+	/// Applications should **never** attempt to read from, write to, or close
+	/// the file descriptor. It is owned by the socket and is only valid for as
+	/// long as the socket remains open.
 	///
 	/// ## Support
 	///
@@ -147,7 +167,8 @@ create_option! {
 	/// of denial-of-service attacks, where a malicious agent can claim to want
 	/// to send an extraordinarily large message, without sending any data.
 	/// This option can be set for the socket, but may be overridden for on a
-	/// per-dialer or per-listener basis.
+	/// per-dialer or per-listener basis. Dialers and listeners that do not
+	/// override it use the socket's value at the time they are created.
 	///
 	/// Note that some transports may have further message size restrictions.
 	///
@@ -176,6 +197,7 @@ create_option! {
 	/// ## Support
 	///
 	/// * Sockets can utilize this value.
+	/// * Contexts can utilize this value, independently of their owning Socket.
 	/// * Dialers and Listeners can retrieve it from their owning Socket.
 	RecvTimeout -> Option<Duration>:
 	Get s = s.getopt_ms(nng_sys::NNG_OPT_RECVTIMEO as *const _ as _);
@@ -187,7 +209,10 @@ create_option! {
 	///
 	/// Messages sent by an application may be buffered by the socket until a
 	/// transport is ready to accept them for delivery. This value must be an
-	/// integer between 0 and 8192, inclusive.
+	/// integer between 0 and 8192, inclusive. Other values are rejected with
+	/// [`InvalidInput`] before reaching NNG.
+	///
+	/// [`InvalidInput`]: ../enum.Error.html#variant.InvalidInput
 	///
 	/// ## Support
 	///
@@ -195,7 +220,7 @@ create_option! {
 	/// * Dialers and Listeners can retrieve it from their owning Socket.
 	SendBufferSize -> i32:
 	Get s = s.getopt_int(nng_sys::NNG_OPT_SENDBUF as *const _ as _);
-	Set s val = s.setopt_int(nng_sys::NNG_OPT_SENDBUF as *const _ as _, val);
+	Set s val = s.setopt_int(nng_sys::NNG_OPT_SENDBUF as *const _ as _, buffer_depth(val)?);
 }
 
 #[cfg(unix)]
@@ -206,11 +231,15 @@ create_option! {
 	/// a message without blocking. When the socket can no longer accept messages
 	/// without blocking, the descriptor will _not_ be readable.
 	///
+	/// Note that the descriptor signals readiness for sending by becoming
+	/// _readable_, not writable, so it must be registered for read events.
+	///
 	/// While this may be useful for integrating into existing polling loops, the
 	/// use of asynchronous I/O objects will be more efficient.
 	///
-	/// Applications should **never** attempt to read or write to the file
-	/// descriptor.
+	/// Applications should **never** attempt to read from, write to, or close
+	/// the file descriptor. It is owned by the socket and is only valid for as
+	/// long as the socket remains open.
 	///
 	/// ## Support
 	///
@@ -229,6 +258,7 @@ create_option! {
 	/// ## Support
 	///
 	/// * Sockets can utilize this value.
+	/// * Contexts can utilize this value, independently of their owning Socket.
 	/// * Dialers and Listeners can retrieve it from their owning Socket.
 	SendTimeout -> Option<Duration>:
 	Get s = s.getopt_ms(nng_sys::NNG_OPT_SENDTIMEO as *const _ as _);
@@ -251,6 +281,47 @@ create_option! {
 	Set s val = s.setopt_string(nng_sys::NNG_OPT_SOCKNAME as *const _ as _, &val);
 }
 
+create_option! {
+	/// The 16-bit number of the protocol used by the socket.
+	///
+	/// ## Support
+	///
+	/// * Sockets can read this value.
+	ProtocolId -> u16:
+	Get s = s.getopt_int(nng_sys::NNG_OPT_PROTO as *const _ as _).map(|v| v as u16);
+}
+
+create_option! {
+	/// The name of the protocol used by the socket, such as `"req"`.
+	///
+	/// ## Support
+	///
+	/// * Sockets can read this value.
+	ProtocolName -> String:
+	Get s = s.getopt_string(nng_sys::NNG_OPT_PROTONAME as *const _ as _);
+}
+
+create_option! {
+	/// The 16-bit number of the protocol that peers of the socket must use.
+	///
+	/// ## Support
+	///
+	/// * Sockets can read this value.
+	PeerId -> u16:
+	Get s = s.getopt_int(nng_sys::NNG_OPT_PEER as *const _ as _).map(|v| v as u16);
+}
+
+create_option! {
+	/// The name of the protocol that peers of the socket must use, such as
+	/// `"rep"`.
+	///
+	/// ## Support
+	///
+	/// * Sockets can read this value.
+	PeerName -> String:
+	Get s = s.getopt_string(nng_sys::NNG_OPT_PEERNAME as *const _ as _);
+}
+
 create_option! {
 	/// The maximum number of "hops" a message may traverse.
 	///
@@ -342,6 +413,22 @@ pub mod protocol
 			/// used. To receive any messages, at least one subscription must
 			/// exist.
 			///
+			/// ```
+			/// use nng::{
+			///     options::{protocol::pubsub::{Subscribe, Unsubscribe}, Options},
+			///     Protocol, Socket,
+			/// };
+			///
+			/// let socket = Socket::new(Protocol::Sub0).unwrap();
+			///
+			/// // Only accept messages that begin with "weather".
+			/// socket.set_opt::<Subscribe>(b"weather".to_vec()).unwrap();
+			///
+			/// // Swap that out for an empty topic, which accepts every message.
+			/// socket.set_opt::<Subscribe>(Vec::new()).unwrap();
+			/// socket.set_opt::<Unsubscribe>(b"weather".to_vec()).unwrap();
+			/// ```
+			///
 			/// ## Support
 			///
 			/// * Sockets can set this option when using the Sub v0 protocol.
@@ -379,6 +466,15 @@ pub mod protocol
 			/// was sent disconnects, or if a peer becomes available while the
 			/// requester is waiting for an available peer.)
 			///
+			/// Setting this to `None` disables the timer, so requests are never
+			/// resent because a reply is slow to arrive. This is the option to
+			/// use when the replier is not idempotent. NNG treats a zero
+			/// duration the same way, but `None` states the intent more
+			/// clearly. Note that neither value prevents the resend that
+			/// happens when the peer holding the request disconnects.
+			///
+			/// The default resend time is one minute.
+			///
 			/// ## Support
 			///
 			/// * Sockets can read and write this value when using the following protocols:
@@ -399,11 +495,17 @@ pub mod protocol
 			/// Amount of time that the following surveys will last.
 			///
 			/// When a new survey is started, a timer of this duration is also
-			/// started. Any responses arriving this time will be discarded.
-			/// Attempts to receive after the timer expires with no other
-			/// surveys started will result in `ErrorKind::IncorrectState`.
-			/// Attempts to receive when this timer expires will result in
-			/// `ErrorKind::TimedOut`.
+			/// started. Any responses arriving after this time will be
+			/// discarded. Attempts to receive after the timer expires with no
+			/// other surveys started will result in `Error::IncorrectState`.
+			/// Receive operations that are pending when the timer expires will
+			/// fail with `Error::TimedOut`.
+			///
+			/// Each new survey restarts the timer. Starting a survey while
+			/// another is still in progress cancels the earlier one, and any
+			/// late responses to it are discarded.
+			///
+			/// The default survey time is one second.
 			///
 			/// ## Support
 			///
@@ -438,12 +540,29 @@ pub mod transport
 			/// POSIX systems will fail to permit a client to connect to a socket located in a
 			/// directory for which the client lacks search (execute) permission.
 			///
-			/// Also consider using the `PeerId` property from within the pipe notify callback to
+			/// Also consider using the `PeerUid` property from within the pipe notify callback to
 			/// validate peer credentials.
 			///
+			/// The permissions are applied when the socket file is created, so this option must be
+			/// set on a `ListenerBuilder` before the listener is started.
+			///
 			/// ## Support
 			///
 			/// * Listeners that are using the IPC protocol.
+			///
+			/// ## Example
+			///
+			/// ```no_run
+			/// use nng::{
+			///     options::{transport::ipc::Permissions, Options},
+			///     ListenerBuilder, Protocol, Socket,
+			/// };
+			///
+			/// let socket = Socket::new(Protocol::Rep0).unwrap();
+			/// let builder = ListenerBuilder::new(&socket, "ipc:///run/daemon.sock").unwrap();
+			/// builder.set_opt::<Permissions>(0o600).unwrap();
+			/// let _listener = builder.start().unwrap();
+			/// ```
 			Permissions -> u32:
 			Set s val = s.setopt_int(nng_sys::NNG_OPT_IPC_PERMISSIONS as *const _ as _, val as _);
 		}
@@ -567,6 +686,25 @@ pub mod transport
 	/// Options related to the TLS transport.
 	pub mod tls
 	{
+		create_option! {
+			/// The TLS configuration used by the endpoint.
+			///
+			/// The configuration is held by the endpoint, so the provided
+			/// value may be dropped once it has been set. Setting this option
+			/// makes the configuration read-only. See the [TLS transport][1]
+			/// documentation for more information.
+			///
+			/// ## Support
+			///
+			/// * Dialers and Listeners can set this option with the following transports:
+			///     * TLS
+			///     * WebSocket (Secure)
+			///
+			/// [1]: https://nanomsg.github.io/nng/man/v1.2.2/nng_tls.7.html
+			Config -> crate::tls::TlsConfig:
+			Set s val = unsafe { s.setopt_ptr(nng_sys::NNG_OPT_TLS_CONFIG as *const _ as _, val.as_ptr() as _) };
+		}
+
 		create_option! {
 			/// Used to load certificates associated associated private key from a
 			/// file.
@@ -0,0 +1,242 @@
+use std::{
+	fmt,
+	ops::{Deref, DerefMut},
+	sync::{Arc, Mutex},
+};
+
+use crate::message::Message;
+
+/// A pool of reusable messages.
+///
+/// Allocating and freeing messages can dominate the profile of applications
+/// that send and receive messages at a high rate. The pool keeps a list of
+/// previously used messages and hands them out again, so that their memory
+/// can be reused instead of going through `nng_msg_alloc` and `nng_msg_free`.
+///
+/// Messages are taken from the pool with [`get`], which returns a
+/// [`PooledMessage`]. When the pooled message is dropped, the message is
+/// cleared and returned to the pool. Converting it into a regular `Message`,
+/// which happens implicitly when it is passed to one of the send functions,
+/// detaches it from the pool. Sent messages are owned (and eventually freed)
+/// by NNG, so they are never returned to the pool twice.
+///
+/// Received messages can be handed to the pool with [`put`] once the
+/// application is done with them.
+///
+/// The pool is cheap to clone and all clones share the same list of messages.
+///
+/// The `pool` example compares a send and receive loop with and without a
+/// pool and can be run with `cargo run --release --example pool`.
+///
+/// ## Example
+///
+/// ```
+/// use nng::{MessagePool, Protocol, Socket};
+///
+/// let pull = Socket::new(Protocol::Pull0).unwrap();
+/// pull.listen("inproc://nng/pool/example").unwrap();
+///
+/// let push = Socket::new(Protocol::Push0).unwrap();
+/// push.dial("inproc://nng/pool/example").unwrap();
+///
+/// let pool = MessagePool::new(16);
+/// for i in 0..4u32 {
+///     let mut msg = pool.get();
+///     msg.push_u32_be(i);
+///     push.send(msg).unwrap();
+///
+///     let reply = pull.recv().unwrap();
+///     assert_eq!(&reply[..], &i.to_be_bytes());
+///     pool.put(reply);
+/// }
+///
+/// assert_eq!(pool.len(), 1);
+/// ```
+///
+/// [`get`]: #method.get
+/// [`put`]: #method.put
+/// [`PooledMessage`]: struct.PooledMessage.html
+#[derive(Clone)]
+pub struct MessagePool
+{
+	/// The state shared between all clones of the pool.
+	inner: Arc<Inner>,
+}
+impl MessagePool
+{
+	/// Creates a new, empty pool that holds at most `max` idle messages.
+	///
+	/// Messages returned to a full pool are freed.
+	pub fn new(max: usize) -> Self
+	{
+		let inner = Inner { free: Mutex::new(Vec::new()), max };
+		MessagePool { inner: Arc::new(inner) }
+	}
+
+	/// Takes an empty message from the pool.
+	///
+	/// If the pool has no idle messages, a new one is allocated.
+	pub fn get(&self) -> PooledMessage
+	{
+		let msg = self.inner.free.lock().unwrap().pop().unwrap_or_else(Message::new);
+		PooledMessage { msg: Some(msg), pool: self.clone() }
+	}
+
+	/// Returns a message to the pool.
+	///
+	/// The body, header, and pipe of the message are cleared. If the pool is
+	/// already full, the message is freed instead.
+	pub fn put(&self, mut msg: Message)
+	{
+		// Clearing the message does not need the lock, so do it before taking it
+		// to keep the critical section as short as possible.
+		msg.reset();
+
+		let mut free = self.inner.free.lock().unwrap();
+		if free.len() < self.inner.max {
+			free.push(msg);
+		}
+	}
+
+	/// Returns the number of idle messages currently held by the pool.
+	pub fn len(&self) -> usize { self.inner.free.lock().unwrap().len() }
+
+	/// Returns true if the pool holds no idle messages.
+	pub fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+impl fmt::Debug for MessagePool
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		f.debug_struct("MessagePool")
+			.field("len", &self.len())
+			.field("max", &self.inner.max)
+			.finish()
+	}
+}
+
+/// A message taken from a [`MessagePool`].
+///
+/// This dereferences to a [`Message`] and is returned to the pool when
+/// dropped. Converting it into a `Message` detaches it from the pool.
+///
+/// [`Message`]: struct.Message.html
+/// [`MessagePool`]: struct.MessagePool.html
+#[derive(Debug)]
+pub struct PooledMessage
+{
+	/// The message, which is only `None` once it has been detached.
+	msg: Option<Message>,
+
+	/// The pool the message is returned to.
+	pool: MessagePool,
+}
+impl PooledMessage
+{
+	/// Detaches the message from the pool.
+	pub fn into_inner(mut self) -> Message
+	{
+		self.msg.take().expect("PooledMessage has already been detached")
+	}
+}
+
+impl Deref for PooledMessage
+{
+	type Target = Message;
+
+	fn deref(&self) -> &Message
+	{
+		self.msg.as_ref().expect("PooledMessage has already been detached")
+	}
+}
+
+impl DerefMut for PooledMessage
+{
+	fn deref_mut(&mut self) -> &mut Message
+	{
+		self.msg.as_mut().expect("PooledMessage has already been detached")
+	}
+}
+
+impl From<PooledMessage> for Message
+{
+	fn from(msg: PooledMessage) -> Message { msg.into_inner() }
+}
+
+impl Drop for PooledMessage
+{
+	fn drop(&mut self)
+	{
+		if let Some(msg) = self.msg.take() {
+			self.pool.put(msg);
+		}
+	}
+}
+
+/// The state shared between clones of a `MessagePool`.
+#[derive(Debug)]
+struct Inner
+{
+	/// The idle messages.
+	free: Mutex<Vec<Message>>,
+
+	/// The maximum number of idle messages.
+	max: usize,
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	#[test]
+	fn put_respects_max()
+	{
+		let pool = MessagePool::new(2);
+		for _ in 0..5 {
+			pool.put(Message::new());
+		}
+
+		assert_eq!(pool.len(), 2);
+	}
+
+	#[test]
+	fn put_resets_message()
+	{
+		let pool = MessagePool::new(1);
+
+		let mut msg = Message::from(&b"body"[..]);
+		msg.as_mut_header().push_back(b"header");
+		pool.put(msg);
+
+		let msg = pool.get();
+		assert!(msg.is_empty());
+		assert!(msg.as_header().is_empty());
+		assert!(msg.pipe().is_none());
+	}
+
+	#[test]
+	fn drop_returns_to_pool()
+	{
+		let pool = MessagePool::new(4);
+
+		let mut msg = pool.get();
+		msg.push_back(b"dropped");
+		drop(msg);
+		assert_eq!(pool.len(), 1);
+
+		// The idle message is handed out again and stays out of the pool once it
+		// has been detached.
+		let msg = pool.get().into_inner();
+		assert!(msg.is_empty());
+		assert!(pool.is_empty());
+
+		drop(msg);
+		assert!(pool.is_empty());
+
+		let msg: Message = pool.get().into();
+		drop(msg);
+		assert!(pool.is_empty());
+	}
+}
@@ -42,7 +42,15 @@ pub enum Protocol
 	/// they have subscribed. See the [publisher/subscriber documentation][1]
 	/// for more information.
 	///
+	/// Sending on a _pub_ socket is always best-effort and never blocks. Each
+	/// subscriber has its own queue, sized by the [`SendBufferSize`] option,
+	/// and a message is silently dropped for any subscriber whose queue is
+	/// full. A slow subscriber therefore cannot apply back-pressure to the
+	/// publisher or to the other subscribers. There is no option to change
+	/// this behavior; increasing the send buffer only makes drops less likely.
+	///
 	/// [1]: https://nanomsg.github.io/nng/man/v1.2.2/nng_pub.7.html
+	/// [`SendBufferSize`]: options/enum.SendBufferSize.html
 	Pub0,
 
 	/// Version 0 of the pull protocol.
@@ -70,6 +78,19 @@ pub enum Protocol
 	///
 	/// See the [pipeline documentation][1] for more information.
 	///
+	/// ```
+	/// use nng::{Protocol, Socket};
+	///
+	/// let pull = Socket::new(Protocol::Pull0).unwrap();
+	/// pull.listen("inproc://nng/protocol/push0").unwrap();
+	///
+	/// let push = Socket::new(Protocol::Push0).unwrap();
+	/// push.dial("inproc://nng/protocol/push0").unwrap();
+	/// push.send(&b"pipeline"[..]).unwrap();
+	///
+	/// assert_eq!(&pull.recv().unwrap()[..], b"pipeline");
+	/// ```
+	///
 	/// [1]: https://nanomsg.github.io/nng/man/v1.2.2/nng_push.7.html
 	Push0,
 
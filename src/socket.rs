@@ -7,17 +7,31 @@ use std::{
 	hash::{Hash, Hasher},
 	num::NonZeroU32,
 	os::raw::{c_int, c_void},
-	ptr,
-	sync::{Arc, RwLock},
+	ptr::{self, NonNull},
+	sync::{
+		atomic::{self, AtomicBool},
+		Arc, RwLock,
+	},
+	time::Duration,
 };
 
+#[cfg(feature = "serde")]
+use crate::codec::{Bincode, Codec, CodecError};
+#[cfg(all(feature = "mio", unix))]
+use std::{io, os::unix::io::RawFd};
 use crate::{
-	aio::Aio,
+	addr::SocketAddr,
+	aio::{Aio, RawAio},
+	dialer::Dialer,
 	error::{Error, Result, SendResult},
+	future::{RecvFuture, SendFuture, Source},
+	listener::Listener,
 	message::Message,
+	options::{private::OptOps, Options, RecvMaxSize, RemAddr, SetOpt},
 	pipe::{Pipe, PipeEvent},
 	protocol::Protocol,
-	util::{abort_unwind, validate_ptr},
+	stats::{stats, StatValue},
+	util::{abort_unwind, duration_to_nng, validate_ptr},
 };
 
 type PipeNotifyFn = dyn Fn(Pipe, PipeEvent) + Send + Sync + 'static;
@@ -31,8 +45,38 @@ type PipeNotifyFn = dyn Fn(Pipe, PipeEvent) + Send + Sync + 'static;
 /// associated with it and is responsible for any state machines or other
 /// application-specific logic.
 ///
+/// Sockets are thread-safe and all operations only require a shared
+/// reference. Cloning a socket produces a new handle to the same underlying
+/// NNG socket, which makes it easy to share a socket between threads.
+///
 /// See the [NNG documentation][1] for more information.
 ///
+/// ## Example
+///
+/// ```
+/// use std::thread;
+/// use nng::{Protocol, Socket};
+///
+/// let pull = Socket::new(Protocol::Pull0).unwrap();
+/// pull.listen("inproc://nng/socket/shared").unwrap();
+///
+/// let push = Socket::new(Protocol::Push0).unwrap();
+/// push.dial("inproc://nng/socket/shared").unwrap();
+///
+/// let threads: Vec<_> = (0..2u8)
+///     .map(|i| {
+///         let push = push.clone();
+///         thread::spawn(move || push.send(&[i][..]).unwrap())
+///     })
+///     .collect();
+///
+/// threads.into_iter().for_each(|t| t.join().unwrap());
+///
+/// let mut received = vec![pull.recv().unwrap()[0], pull.recv().unwrap()[0]];
+/// received.sort();
+/// assert_eq!(received, [0, 1]);
+/// ```
+///
 /// [1]: https://nanomsg.github.io/nng/man/v1.2.2/nng_socket.5.html
 #[derive(Clone, Debug)]
 pub struct Socket
@@ -44,13 +88,20 @@ impl Socket
 {
 	/// Creates a new socket which uses the specified protocol.
 	///
+	/// The socket is opened in "cooked" mode, where the protocol state machine
+	/// is handled by NNG. Sockets that need to be opened in "raw" mode, such as
+	/// those passed to [`forwarder`], should be created with
+	/// [`RawSocket::new`] instead.
+	///
 	/// # Errors
 	///
 	/// * [`NotSupported`]: Protocol is not enabled.
 	/// * [`OutOfMemory`]: Insufficient memory available.
 	///
+	/// [`forwarder`]: fn.forwarder.html
 	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
 	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	/// [`RawSocket::new`]: struct.RawSocket.html#method.new
 	pub fn new(t: Protocol) -> Result<Socket>
 	{
 		// Create the uninitialized nng_socket
@@ -73,9 +124,7 @@ impl Socket
 			}
 		};
 
-		rv2res!(rv, Socket {
-			inner: Arc::new(Inner { handle: socket, pipe_notify: RwLock::new(None) }),
-		})
+		rv2res!(rv, Socket { inner: Arc::new(Inner::new(socket)) })
 	}
 
 	/// Initiates a remote connection to a listener.
@@ -95,7 +144,7 @@ impl Socket
 	/// Because the dialer is started immediately, it is generally not possible
 	/// to apply extra configuration. If that is needed, or if one wishes to
 	/// close the dialer before the socket, applications should consider using
-	/// the `Dialer` type directly.
+	/// the [`DialerBuilder`] type directly.
 	///
 	/// See the [NNG documentation][1] for more information.
 	///
@@ -106,6 +155,7 @@ impl Socket
 	/// * [`ConnectionRefused`]: The remote peer refused the connection.
 	/// * [`ConnectionReset`]: The remote peer reset the connection.
 	/// * [`DestUnreachable`]: The remote address is not reachable.
+	/// * [`IncorrectState`]: The socket requires a receive size limit and none is set.
 	/// * [`OutOfMemory`]: Insufficient memory is available.
 	/// * [`PeerAuth`]: Authentication or authorization failure.
 	/// * [`Protocol`]: A protocol error occurred.
@@ -119,9 +169,12 @@ impl Socket
 	/// [`DestUnreachable`]: enum.Error.html#variant.DestUnreachable
 	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
 	/// [`PeerAuth`]: enum.Error.html#variant.PeerAuth
+	/// [`DialerBuilder`]: struct.DialerBuilder.html
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
 	/// [`Protocol`]: enum.Error.html#variant.Protocol
 	pub fn dial(&self, url: &str) -> Result<()>
 	{
+		self.check_recv_limit()?;
 		let addr = CString::new(url).map_err(|_| Error::AddressInvalid)?;
 		let rv = unsafe { nng_sys::nng_dial(self.inner.handle, addr.as_ptr(), ptr::null_mut(), 0) };
 
@@ -141,8 +194,8 @@ impl Socket
 	///
 	/// Because the listener is started immediately, it is generally not
 	/// possible to apply extra configuration. If that is needed, or if one
-	/// wishes to close the dialer before the socket, applications should
-	/// consider using the `Listener` type directly.
+	/// wishes to close the listener before the socket, applications should
+	/// consider using the [`ListenerBuilder`] type directly.
 	///
 	/// See the [NNG documentation][1] for more information.
 	///
@@ -151,15 +204,19 @@ impl Socket
 	/// * [`AddressInUse`]: The address specified by _url_ is already in use.
 	/// * [`AddressInvalid`]: An invalid _url_ was specified.
 	/// * [`Closed`]: The socket is not open.
+	/// * [`IncorrectState`]: The socket requires a receive size limit and none is set.
 	/// * [`OutOfMemory`]: Insufficient memory is available.
 	///
 	/// [1]: https://nanomsg.github.io/nng/man/v1.2.2/nng_listen.3.html
 	/// [`AddressInUse`]: enum.Error.html#variant.AddressInUse
-	/// [`Addressinvalid`]: enum.Error.html#variant.Addressinvalid
+	/// [`AddressInvalid`]: enum.Error.html#variant.AddressInvalid
 	/// [`Closed`]: enum.Error.html#variant.Closed
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
+	/// [`ListenerBuilder`]: struct.ListenerBuilder.html
 	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
 	pub fn listen(&self, url: &str) -> Result<()>
 	{
+		self.check_recv_limit()?;
 		let addr = CString::new(url).map_err(|_| Error::AddressInvalid)?;
 		let rv =
 			unsafe { nng_sys::nng_listen(self.inner.handle, addr.as_ptr(), ptr::null_mut(), 0) };
@@ -176,7 +233,7 @@ impl Socket
 	/// Because the dialer is started immediately, it is generally not possible
 	/// to apply extra configuration. If that is needed, or if one wishes to
 	/// close the dialer before the socket, applications should consider using
-	/// the `Dialer` type directly.
+	/// the [`DialerBuilder`] type directly.
 	///
 	/// See the [NNG documentation][1] for more information.
 	///
@@ -187,6 +244,7 @@ impl Socket
 	/// * [`ConnectionRefused`]: The remote peer refused the connection.
 	/// * [`ConnectionReset`]: The remote peer reset the connection.
 	/// * [`DestUnreachable`]: The remote address is not reachable.
+	/// * [`IncorrectState`]: The socket requires a receive size limit and none is set.
 	/// * [`OutOfMemory`]: Insufficient memory is available.
 	/// * [`PeerAuth`]: Authentication or authorization failure.
 	/// * [`Protocol`]: A protocol error occurred.
@@ -200,12 +258,12 @@ impl Socket
 	/// [`DestUnreachable`]: enum.Error.html#variant.DestUnreachable
 	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
 	/// [`PeerAuth`]: enum.Error.html#variant.PeerAuth
+	/// [`DialerBuilder`]: struct.DialerBuilder.html
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
 	/// [`Protocol`]: enum.Error.html#variant.Protocol
-	///
-	///
-	/// [1]: https://nanomsg.github.io/nng/man/v1.2.2/nng_dial.3.html
 	pub fn dial_async(&self, url: &str) -> Result<()>
 	{
+		self.check_recv_limit()?;
 		let addr = CString::new(url).map_err(|_| Error::AddressInvalid)?;
 		let flags = nng_sys::NNG_FLAG_NONBLOCK as c_int;
 		let rv =
@@ -218,6 +276,92 @@ impl Socket
 	#[deprecated(since = "1.0.0-rc.1", note = "This is equivalent to `Socket::listen`")]
 	pub fn listen_async(&self, url: &str) -> Result<()> { self.listen(url) }
 
+	/// Initiates and starts a dialer for each of the specified addresses.
+	///
+	/// Each address is dialed synchronously in order, as with [`dial`]. If any
+	/// of them fails, the dialers that were already started by this call are
+	/// closed again, so either all of the addresses are dialed or none of them
+	/// are. Connections that were established by the closed dialers are closed
+	/// as well.
+	///
+	/// # Errors
+	///
+	/// On failure, the index of the address that could not be dialed is
+	/// returned along with any error that [`dial`] can return. If the check
+	/// enabled by [`require_recv_limit`] fails, no address is dialed and the
+	/// index is `None`.
+	///
+	/// [`dial`]: #method.dial
+	/// [`require_recv_limit`]: #method.require_recv_limit
+	pub fn dial_all(&self, urls: &[&str]) -> std::result::Result<(), (Option<usize>, Error)>
+	{
+		self.check_recv_limit().map_err(|e| (None, e))?;
+
+		let mut dialers = Vec::with_capacity(urls.len());
+		for (i, url) in urls.iter().enumerate() {
+			match Dialer::new(self, url, false) {
+				Ok(d) => dialers.push(d),
+				Err(e) => {
+					dialers.into_iter().for_each(Dialer::close);
+					return Err((Some(i), e));
+				},
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Initiates and starts a listener on each of the specified addresses.
+	///
+	/// Each address is bound in order, as with [`listen`]. If any of them
+	/// fails, the listeners that were already started by this call are closed
+	/// again, so either the socket is listening on all of the addresses or on
+	/// none of them.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Error, Protocol, Socket};
+	///
+	/// let s = Socket::new(Protocol::Rep0).unwrap();
+	/// s.listen_all(&["inproc://nng/socket/listen_all", "inproc://nng/socket/listen_all2"])
+	///     .unwrap();
+	///
+	/// // The second address is already in use, so neither address is bound.
+	/// let urls = ["inproc://nng/socket/listen_all3", "inproc://nng/socket/listen_all"];
+	/// assert_eq!(s.listen_all(&urls), Err((Some(1), Error::AddressInUse)));
+	///
+	/// // The first address was released again, so it can still be bound.
+	/// s.listen("inproc://nng/socket/listen_all3").unwrap();
+	/// ```
+	///
+	/// # Errors
+	///
+	/// On failure, the index of the address that could not be bound is
+	/// returned along with any error that [`listen`] can return. If the check
+	/// enabled by [`require_recv_limit`] fails, no address is bound and the
+	/// index is `None`.
+	///
+	/// [`listen`]: #method.listen
+	/// [`require_recv_limit`]: #method.require_recv_limit
+	pub fn listen_all(&self, urls: &[&str]) -> std::result::Result<(), (Option<usize>, Error)>
+	{
+		self.check_recv_limit().map_err(|e| (None, e))?;
+
+		let mut listeners = Vec::with_capacity(urls.len());
+		for (i, url) in urls.iter().enumerate() {
+			match Listener::new(self, url) {
+				Ok(l) => listeners.push(l),
+				Err(e) => {
+					listeners.into_iter().for_each(Listener::close);
+					return Err((Some(i), e));
+				},
+			}
+		}
+
+		Ok(())
+	}
+
 	/// Receives a message from the socket.
 	///
 	/// The semantics of what receiving a message means vary from protocol to
@@ -229,7 +373,8 @@ impl Socket
 	/// # Errors
 	///
 	/// * [`Closed`]: The socket is not open.
-	/// * [`IncorrectState`]: The socket cannot receive data in this state.
+	/// * [`IncorrectState`]: The socket cannot receive data in this state. For a
+	///   _surveyor_, this means the survey time has expired and the survey is over.
 	/// * [`NotSupported`]: The protocol does not support receiving.
 	/// * [`OutOfMemory`]: Insufficient memory is available.
 	/// * [`TimedOut`]: The operation timed out.
@@ -248,6 +393,130 @@ impl Socket
 		Ok(Message::from_ptr(msgp))
 	}
 
+	/// Receives a message from the socket directly into the provided buffer.
+	///
+	/// This behaves like [`recv`] but copies the message body into `buf`
+	/// instead of returning a `Message`, which avoids allocating on the Rust
+	/// side in receive loops. On success, the number of bytes received is
+	/// returned.
+	///
+	/// If the message is larger than the buffer, the first `buf.len()` bytes
+	/// are copied and the remainder of the message is discarded. In this case,
+	/// [`MessageTooLarge`] is returned.
+	///
+	/// # Errors
+	///
+	/// * [`Closed`]: The socket is not open.
+	/// * [`IncorrectState`]: The socket cannot receive data in this state.
+	/// * [`MessageTooLarge`]: The message did not fit in the buffer.
+	/// * [`NotSupported`]: The protocol does not support receiving.
+	/// * [`OutOfMemory`]: Insufficient memory is available.
+	/// * [`TimedOut`]: The operation timed out.
+	///
+	/// [`Closed`]: enum.Error.html#variant.Closed
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
+	/// [`MessageTooLarge`]: enum.Error.html#variant.MessageTooLarge
+	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	/// [`recv`]: #method.recv
+	/// [`TimedOut`]: enum.Error.html#variant.TimedOut
+	pub fn recv_into(&self, buf: &mut [u8]) -> Result<usize>
+	{
+		// Without the `NNG_FLAG_ALLOC` flag, NNG copies as much of the message as
+		// fits and then sets the size to the full length of the message.
+		let mut size = buf.len();
+		let rv = unsafe {
+			nng_sys::nng_recv(self.inner.handle, buf.as_mut_ptr() as _, &mut size as _, 0)
+		};
+		rv2res!(rv)?;
+
+		if size > buf.len() { Err(Error::MessageTooLarge) } else { Ok(size) }
+	}
+
+	/// Receives a message from the socket into an existing `Message`.
+	///
+	/// This behaves like [`recv`] but stores the received message in `msg`,
+	/// which allows a single long-lived `Message` to be reused in a receive
+	/// loop. On success, the previous contents of `msg`, including its header,
+	/// are freed and replaced by the newly received message. If an error
+	/// occurs, `msg` is left untouched.
+	///
+	/// Note that NNG still allocates the underlying `nng_msg` for every
+	/// received message. This only avoids creating and dropping a new Rust
+	/// `Message` value on each iteration.
+	///
+	/// # Errors
+	///
+	/// * [`Closed`]: The socket is not open.
+	/// * [`IncorrectState`]: The socket cannot receive data in this state.
+	/// * [`NotSupported`]: The protocol does not support receiving.
+	/// * [`OutOfMemory`]: Insufficient memory is available.
+	/// * [`TimedOut`]: The operation timed out.
+	///
+	/// [`Closed`]: enum.Error.html#variant.Closed
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
+	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	/// [`recv`]: #method.recv
+	/// [`TimedOut`]: enum.Error.html#variant.TimedOut
+	pub fn recv_msg_into(&self, msg: &mut Message) -> Result<()>
+	{
+		let mut msgp: *mut nng_sys::nng_msg = ptr::null_mut();
+		let rv = unsafe { nng_sys::nng_recvmsg(self.inner.handle, &mut msgp as _, 0) };
+
+		// Assigning drops the old message, which frees its `nng_msg`.
+		let msgp = validate_ptr(rv, msgp)?;
+		*msg = Message::from_ptr(msgp);
+		Ok(())
+	}
+
+	/// Receives a message from the socket, waiting at most the given duration.
+	///
+	/// This behaves like [`recv`], except that the receive timeout of the
+	/// socket is ignored in favor of `dur`. The operation runs on an internal
+	/// `Aio` object, so the socket's [`RecvTimeout`] option is never modified
+	/// and this may safely be called while other threads use the socket.
+	///
+	/// # Errors
+	///
+	/// * [`Closed`]: The socket is not open.
+	/// * [`IncorrectState`]: The socket cannot receive data in this state.
+	/// * [`NotSupported`]: The protocol does not support receiving.
+	/// * [`OutOfMemory`]: Insufficient memory is available.
+	/// * [`TimedOut`]: No message arrived within `dur`.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Error, Protocol, Socket};
+	/// use std::time::Duration;
+	///
+	/// let socket = Socket::new(Protocol::Pair0).unwrap();
+	/// let res = socket.recv_timeout_once(Duration::from_millis(10));
+	/// assert_eq!(res.unwrap_err(), Error::TimedOut);
+	/// ```
+	///
+	/// [`Closed`]: enum.Error.html#variant.Closed
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
+	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	/// [`recv`]: #method.recv
+	/// [`RecvTimeout`]: options/enum.RecvTimeout.html
+	/// [`TimedOut`]: enum.Error.html#variant.TimedOut
+	pub fn recv_timeout_once(&self, dur: Duration) -> Result<Message>
+	{
+		let aio = RawAio::new()?;
+		unsafe {
+			nng_sys::nng_aio_set_timeout(aio.as_ptr(), duration_to_nng(Some(dur)));
+			nng_sys::nng_recv_aio(self.inner.handle, aio.as_ptr());
+		}
+		aio.wait()?;
+
+		// The message is owned by the `Aio` until it is taken here.
+		let msgp = unsafe { nng_sys::nng_aio_get_msg(aio.as_ptr()) };
+		Ok(Message::from_ptr(NonNull::new(msgp).unwrap()))
+	}
+
 	/// Sends a message on the socket.
 	///
 	/// The semantics of what sending a message means vary from protocol to
@@ -294,6 +563,32 @@ impl Socket
 		}
 	}
 
+	/// Sends a message on the socket to a specific peer.
+	///
+	/// This sets the pipe of the message with [`Message::set_pipe`] and then
+	/// sends it as with [`send`]. Only some protocols honor the pipe of an
+	/// outgoing message, such as _pair_ v1 in polyamorous mode and raw sockets
+	/// of protocols that route replies by pipe. All other protocols ignore the
+	/// pipe and deliver the message as they normally would.
+	///
+	/// If the pipe has been closed, the message is typically discarded without
+	/// an error being reported, as with any other message that cannot be
+	/// delivered.
+	///
+	/// # Errors
+	///
+	/// Any error that [`send`] can return.
+	///
+	/// [`Message::set_pipe`]: struct.Message.html#method.set_pipe
+	/// [`send`]: #method.send
+	pub fn send_to<M: Into<Message>>(&self, msg: M, pipe: Pipe) -> SendResult<()>
+	{
+		let mut msg = msg.into();
+		msg.set_pipe(pipe);
+
+		self.send(msg)
+	}
+
 	/// Attempts to receives a message from the socket.
 	///
 	/// The semantics of what receiving a message means vary from protocol to
@@ -376,6 +671,272 @@ impl Socket
 		}
 	}
 
+	/// Returns an iterator over the messages received on the socket.
+	///
+	/// Each call to `next` blocks in the same way as [`recv`], including
+	/// respecting the socket's receive timeout. Errors, such as [`TimedOut`],
+	/// are yielded to the caller and do not end the iteration. The iterator
+	/// only ends once the socket has been closed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Protocol, Socket};
+	///
+	/// let pull = Socket::new(Protocol::Pull0).unwrap();
+	/// pull.listen("inproc://nng/socket/incoming").unwrap();
+	///
+	/// let push = Socket::new(Protocol::Push0).unwrap();
+	/// push.dial("inproc://nng/socket/incoming").unwrap();
+	/// push.send(&b"one"[..]).unwrap();
+	/// push.send(&b"two"[..]).unwrap();
+	///
+	/// let received: Vec<_> = pull.incoming().take(2).map(|m| m.unwrap()).collect();
+	/// assert_eq!(&received[0][..], b"one");
+	/// assert_eq!(&received[1][..], b"two");
+	/// ```
+	///
+	/// [`recv`]: #method.recv
+	/// [`TimedOut`]: enum.Error.html#variant.TimedOut
+	pub fn incoming(&self) -> Incoming { Incoming { socket: self } }
+
+	/// Sends a survey and collects all of the responses.
+	///
+	/// This is intended for use with _surveyor_ sockets. The message is sent
+	/// and responses are received until the survey time, configured by the
+	/// [`SurveyTime`] option, expires. The responses are returned in the order
+	/// in which they arrived.
+	///
+	/// Each response remembers the [`Pipe`] it was received on, which can be
+	/// used to tell the responders apart. To get the address of each
+	/// responder as well, use [`survey_with_addrs`].
+	///
+	/// Note that the receive timeout of the socket also applies to each
+	/// individual receive. If it is shorter than the survey time, collection
+	/// stops early once no response arrives within that timeout.
+	///
+	/// # Errors
+	///
+	/// Any error that [`send`] or [`recv`] can return, other than the
+	/// [`TimedOut`] and [`IncorrectState`] errors that signal the end of the
+	/// survey. If sending fails, the message is discarded.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Protocol, Socket};
+	/// use nng::options::{protocol::survey::SurveyTime, Options};
+	/// use std::time::Duration;
+	///
+	/// let surveyor = Socket::new(Protocol::Surveyor0).unwrap();
+	/// surveyor.set_opt::<SurveyTime>(Some(Duration::from_millis(50))).unwrap();
+	/// surveyor.listen("inproc://nng/socket/survey").unwrap();
+	///
+	/// // Nobody is listening yet, so the survey simply times out.
+	/// let responses = surveyor.survey(&b"ping"[..]).unwrap();
+	/// assert!(responses.is_empty());
+	/// ```
+	///
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
+	/// [`Pipe`]: struct.Pipe.html
+	/// [`recv`]: #method.recv
+	/// [`send`]: #method.send
+	/// [`survey_with_addrs`]: #method.survey_with_addrs
+	/// [`SurveyTime`]: options/protocol/survey/enum.SurveyTime.html
+	/// [`TimedOut`]: enum.Error.html#variant.TimedOut
+	pub fn survey<M: Into<Message>>(&self, msg: M) -> Result<Vec<Message>>
+	{
+		self.survey_map(msg, |msg| msg)
+	}
+
+	/// Sends a survey and collects all of the responses along with the address
+	/// of each responder.
+	///
+	/// This behaves like [`survey`], except that the [`RemAddr`] option of the
+	/// pipe each response arrived on is read as soon as the response is
+	/// received. The address is `None` if the pipe has already been closed by
+	/// then or if the transport does not report the remote address.
+	///
+	/// # Errors
+	///
+	/// The same errors as [`survey`].
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Listener, Protocol, Socket};
+	/// use nng::options::{protocol::survey::SurveyTime, Options};
+	/// use std::{thread, time::Duration};
+	///
+	/// let surveyor = Socket::new(Protocol::Surveyor0).unwrap();
+	/// surveyor.set_opt::<SurveyTime>(Some(Duration::from_millis(500))).unwrap();
+	/// let url = Listener::new(&surveyor, "tcp://127.0.0.1:0").unwrap().local_url().unwrap();
+	///
+	/// let respondent = Socket::new(Protocol::Respondent0).unwrap();
+	/// respondent.dial(url.as_str()).unwrap();
+	/// let worker = thread::spawn(move || {
+	///     let msg = respondent.recv().unwrap();
+	///     respondent.send(msg).unwrap();
+	/// });
+	///
+	/// // Surveys only reach the peers that are already connected.
+	/// while surveyor.pipe_count().unwrap() == 0 {
+	///     thread::sleep(Duration::from_millis(10));
+	/// }
+	///
+	/// let responses = surveyor.survey_with_addrs(&b"ping"[..]).unwrap();
+	/// assert_eq!(responses.len(), 1);
+	/// assert!(responses[0].0.is_some());
+	/// assert_eq!(&responses[0].1[..], b"ping");
+	/// worker.join().unwrap();
+	/// ```
+	///
+	/// [`RemAddr`]: options/enum.RemAddr.html
+	/// [`survey`]: #method.survey
+	pub fn survey_with_addrs<M: Into<Message>>(
+		&self,
+		msg: M,
+	) -> Result<Vec<(Option<SocketAddr>, Message)>>
+	{
+		self.survey_map(msg, |msg| {
+			let addr = msg.pipe().and_then(|p| p.get_opt::<RemAddr>().ok());
+			(addr, msg)
+		})
+	}
+
+	/// Sends a survey and collects the responses, passing each one through `f`
+	/// as it arrives.
+	fn survey_map<M, T, F>(&self, msg: M, mut f: F) -> Result<Vec<T>>
+	where
+		M: Into<Message>,
+		F: FnMut(Message) -> T,
+	{
+		self.send(msg).map_err(|(_, e)| e)?;
+
+		let mut responses = Vec::new();
+		loop {
+			match self.recv() {
+				Ok(msg) => responses.push(f(msg)),
+				Err(Error::TimedOut) | Err(Error::IncorrectState) => return Ok(responses),
+				Err(e) => return Err(e),
+			}
+		}
+	}
+
+	/// Sends a request and waits for the reply.
+	///
+	/// This is intended for use with _req_ sockets and is equivalent to a call
+	/// to [`send`] followed by a call to [`recv`]. Both halves honor the send
+	/// and receive timeouts of the socket. The _req_ protocol only delivers the
+	/// reply to the most recent request, so the returned message is always the
+	/// reply to this request.
+	///
+	/// Sockets are shared between threads, so concurrent requests on the same
+	/// socket will cancel each other. Use a [`Context`] per thread instead.
+	///
+	/// # Errors
+	///
+	/// Any error that [`send`] or [`recv`] can return. In particular,
+	/// [`TimedOut`] is returned if no reply arrives before the receive timeout
+	/// expires. If sending fails, the message is discarded.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Protocol, Socket};
+	/// use std::thread;
+	///
+	/// let server = Socket::new(Protocol::Rep0).unwrap();
+	/// server.listen("inproc://nng/socket/request").unwrap();
+	///
+	/// let client = Socket::new(Protocol::Req0).unwrap();
+	/// client.dial("inproc://nng/socket/request").unwrap();
+	///
+	/// let jh = thread::spawn(move || {
+	///     let mut msg = server.recv().unwrap();
+	///     msg.push_back(b" pong");
+	///     server.send(msg).unwrap();
+	/// });
+	///
+	/// let reply = client.request(&b"ping"[..]).unwrap();
+	/// assert_eq!(&reply[..], b"ping pong");
+	/// jh.join().unwrap();
+	/// ```
+	///
+	/// [`Context`]: struct.Context.html
+	/// [`recv`]: #method.recv
+	/// [`send`]: #method.send
+	/// [`TimedOut`]: enum.Error.html#variant.TimedOut
+	pub fn request<M: Into<Message>>(&self, msg: M) -> Result<Message>
+	{
+		self.send(msg).map_err(|(_, e)| e)?;
+		self.recv()
+	}
+
+	/// Receives messages and replies to them until the socket is closed.
+	///
+	/// This captures the server loop of the _rep_ and _respondent_ protocols.
+	/// Each received request or survey is passed to `f`, and the message it
+	/// returns is sent back as the reply. Returning `None` skips the reply,
+	/// which for a _respondent_ means not answering that survey.
+	///
+	/// The loop ends cleanly with `Ok(())` once the socket is closed, for
+	/// example by calling [`close`] on a clone of the socket from another
+	/// thread. To stop on some other signal, set a receive timeout with
+	/// [`RecvTimeout`]: the loop then returns [`TimedOut`] whenever no message
+	/// arrives in time, and the caller can check its own stop condition before
+	/// calling this function again.
+	///
+	/// # Errors
+	///
+	/// Any error that [`send`] or [`recv`] can return, other than [`Closed`].
+	/// If sending a reply fails, the reply is discarded.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Error, Protocol, Socket};
+	/// use nng::options::{Options, RecvTimeout};
+	/// use std::time::Duration;
+	///
+	/// let respondent = Socket::new(Protocol::Respondent0).unwrap();
+	/// respondent.set_opt::<RecvTimeout>(Some(Duration::from_millis(50))).unwrap();
+	/// respondent.dial_async("inproc://nng/socket/respond_with").unwrap();
+	///
+	/// // Nobody sends a survey, so control returns once the timeout expires.
+	/// let res = respondent.respond_with(|mut msg| {
+	///     msg.push_back(b" pong");
+	///     Some(msg)
+	/// });
+	/// assert_eq!(res, Err(Error::TimedOut));
+	/// ```
+	///
+	/// [`close`]: #method.close
+	/// [`Closed`]: enum.Error.html#variant.Closed
+	/// [`recv`]: #method.recv
+	/// [`RecvTimeout`]: options/enum.RecvTimeout.html
+	/// [`send`]: #method.send
+	/// [`TimedOut`]: enum.Error.html#variant.TimedOut
+	pub fn respond_with<F>(&self, mut f: F) -> Result<()>
+	where
+		F: FnMut(Message) -> Option<Message>,
+	{
+		loop {
+			let msg = match self.recv() {
+				Ok(m) => m,
+				Err(Error::Closed) => return Ok(()),
+				Err(e) => return Err(e),
+			};
+
+			if let Some(reply) = f(msg) {
+				match self.send(reply) {
+					Ok(()) | Err((_, Error::Closed)) => {},
+					Err((_, e)) => return Err(e),
+				}
+			}
+		}
+	}
+
 	/// Start a receive operation using the given `Aio` and return immediately.
 	///
 	/// # Errors
@@ -398,12 +959,71 @@ impl Socket
 		aio.send_socket(self, msg)
 	}
 
+	/// Returns a future that receives a message from the socket.
+	///
+	/// The future owns its own `Aio` object and a handle to the socket, so it
+	/// is independent of any other operations on the socket. The receive is
+	/// started when the future is first polled and is canceled if the future
+	/// is dropped before completing. The result is the same as [`recv`].
+	///
+	/// [`recv`]: #method.recv
+	pub fn recv_future(&self) -> RecvFuture { RecvFuture::new(Source::Socket(self.clone())) }
+
+	/// Returns a future that sends the message on the socket.
+	///
+	/// The future owns its own `Aio` object and a handle to the socket, so it
+	/// is independent of any other operations on the socket. The send is
+	/// started when the future is first polled and is canceled if the future
+	/// is dropped before completing. The result is the same as [`send`].
+	///
+	/// [`send`]: #method.send
+	pub fn send_future<M: Into<Message>>(&self, msg: M) -> SendFuture
+	{
+		SendFuture::new(Source::Socket(self.clone()), msg.into())
+	}
+
 	/// Register a callback function to be called whenever a pipe event occurs
 	/// on the socket.
 	///
 	/// Only a single callback function can be supplied at a time. Registering a
 	/// new callback implicitly unregisters any previously registered.
 	///
+	/// Closing the pipe from within the callback during the
+	/// [`PipeEvent::AddPre`] event rejects the connection before the socket
+	/// ever sees it.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use std::{
+	///     collections::HashSet,
+	///     sync::{Arc, Mutex},
+	/// };
+	/// use nng::{PipeEvent, Protocol, Socket};
+	///
+	/// const MAX_PEERS: usize = 8;
+	///
+	/// let socket = Socket::new(Protocol::Bus0).unwrap();
+	/// let roster = Arc::new(Mutex::new(HashSet::new()));
+	/// let callback_roster = Arc::clone(&roster);
+	///
+	/// socket
+	///     .pipe_notify(move |pipe, event| {
+	///         let mut roster = callback_roster.lock().unwrap();
+	///         match event {
+	///             PipeEvent::AddPre if roster.len() >= MAX_PEERS => pipe.close(),
+	///             PipeEvent::AddPost => {
+	///                 roster.insert(pipe);
+	///             },
+	///             PipeEvent::RemovePost => {
+	///                 roster.remove(&pipe);
+	///             },
+	///             _ => {},
+	///         }
+	///     })
+	///     .unwrap();
+	/// ```
+	///
 	/// # Errors
 	///
 	/// None specified.
@@ -416,6 +1036,9 @@ impl Socket
 	/// produce the abort in order to keep things consistent. As such, the user
 	/// is responsible for either having a callback that never panics or
 	/// catching and handling the panic within the callback.
+	///
+	///
+	/// [`PipeEvent::AddPre`]: enum.PipeEvent.html#variant.AddPre
 	pub fn pipe_notify<F>(&self, callback: F) -> Result<()>
 	where
 		F: Fn(Pipe, PipeEvent) + Send + Sync + 'static,
@@ -456,6 +1079,223 @@ impl Socket
 			.fold(Ok(()), std::result::Result::and)
 	}
 
+	/// Returns the number of pipes currently attached to the socket.
+	///
+	/// The count is read from a fresh snapshot of the NNG [statistics][1], so it
+	/// reflects the state of the socket at the time of the call and may already
+	/// be out of date by the time it is returned. Pipes that are still being
+	/// negotiated are not included.
+	///
+	/// # Errors
+	///
+	/// * [`EntryNotFound`]: The socket is closed or does not report a pipe count.
+	/// * [`NotSupported`]: Statistics are not supported by the library.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Protocol, Socket};
+	/// use std::{thread, time::Duration};
+	///
+	/// let listener = Socket::new(Protocol::Pair0).unwrap();
+	/// listener.listen("inproc://nng/socket/pipe_count").unwrap();
+	///
+	/// let dialer = Socket::new(Protocol::Pair0).unwrap();
+	/// assert_eq!(dialer.pipe_count().unwrap(), 0);
+	///
+	/// // The statistics are updated as NNG attaches the pipe, which is not
+	/// // guaranteed to be finished when `dial` returns, so poll for a while.
+	/// dialer.dial("inproc://nng/socket/pipe_count").unwrap();
+	/// let mut count = 0;
+	/// for _ in 0..100 {
+	///     count = dialer.pipe_count().unwrap();
+	///     if count == 1 {
+	///         break;
+	///     }
+	///     thread::sleep(Duration::from_millis(10));
+	/// }
+	/// assert_eq!(count, 1);
+	/// ```
+	///
+	/// [1]: fn.stats.html
+	/// [`EntryNotFound`]: enum.Error.html#variant.EntryNotFound
+	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	pub fn pipe_count(&self) -> Result<u64>
+	{
+		let id = unsafe { nng_sys::nng_socket_id(self.inner.handle) };
+		let snapshot = stats()?;
+
+		// Each socket has its own scope beneath the root, identified by the "id"
+		// statistic and containing a "pipes" statistic with the current count. The
+		// name of the scope is either "socket" or "socket" followed by the ID,
+		// depending on the version of NNG.
+		let scope = snapshot
+			.root()
+			.children()
+			.find(|scope| {
+				scope.name().starts_with("socket")
+					&& scope.children().any(|stat| {
+						stat.name() == "id" && stat.value() == StatValue::Id(id as u64)
+					})
+			})
+			.ok_or(Error::EntryNotFound)?;
+
+		scope
+			.children()
+			.filter(|stat| stat.name() == "pipes")
+			.find_map(|stat| match stat.value() {
+				StatValue::Level(n) | StatValue::Counter(n) => Some(n),
+				_ => None,
+			})
+			.ok_or(Error::EntryNotFound)
+	}
+
+	/// Returns the URLs of the dialers that belong to the socket.
+	///
+	/// This includes dialers created by [`dial`] as well as those created
+	/// directly. Like [`pipe_count`], the URLs are read from a snapshot of the
+	/// NNG statistics and only reflect the dialers open at the time of the
+	/// call.
+	///
+	/// # Errors
+	///
+	/// * [`NotSupported`]: Statistics are not supported by the library.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// [`dial`]: #method.dial
+	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	/// [`pipe_count`]: #method.pipe_count
+	pub fn dialer_urls(&self) -> Result<Vec<String>> { self.endpoint_urls("dialer") }
+
+	/// Returns the URLs of the listeners that belong to the socket.
+	///
+	/// This includes listeners created by [`listen`] as well as those created
+	/// directly. Like [`pipe_count`], the URLs are read from a snapshot of the
+	/// NNG statistics and only reflect the listeners open at the time of the
+	/// call.
+	///
+	/// # Errors
+	///
+	/// * [`NotSupported`]: Statistics are not supported by the library.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Protocol, Socket};
+	///
+	/// let s = Socket::new(Protocol::Pair0).unwrap();
+	/// s.listen("inproc://nng/socket/listener_urls").unwrap();
+	///
+	/// assert_eq!(s.listener_urls().unwrap(), ["inproc://nng/socket/listener_urls"]);
+	/// assert!(s.dialer_urls().unwrap().is_empty());
+	/// ```
+	///
+	/// [`listen`]: #method.listen
+	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	/// [`pipe_count`]: #method.pipe_count
+	pub fn listener_urls(&self) -> Result<Vec<String>> { self.endpoint_urls("listener") }
+
+	/// Returns the URLs of the endpoints of the given kind from the statistics.
+	fn endpoint_urls(&self, kind: &str) -> Result<Vec<String>>
+	{
+		let id = unsafe { nng_sys::nng_socket_id(self.inner.handle) };
+		let snapshot = stats()?;
+
+		// Dialers and listeners have their own scopes beneath the root, which
+		// refer back to the owning socket through the "socket" statistic.
+		let urls = snapshot
+			.root()
+			.children()
+			.filter(|scope| scope.name().starts_with(kind))
+			.filter(|scope| {
+				scope.children().any(|stat| {
+					stat.name() == "socket" && stat.value() == StatValue::Id(id as u64)
+				})
+			})
+			.filter_map(|scope| {
+				scope.children().filter(|stat| stat.name() == "url").find_map(|stat| {
+					match stat.value() {
+						StatValue::String(url) => Some(url.into_owned()),
+						_ => None,
+					}
+				})
+			})
+			.collect();
+
+		Ok(urls)
+	}
+
+	/// Requires a receive size limit before the socket dials or listens.
+	///
+	/// By default, the [`RecvMaxSize`] option is zero, meaning that peers may
+	/// send messages of any size. Once this function has been called, every
+	/// way of dialing or listening on the socket fails with [`IncorrectState`]
+	/// while that option is still zero. This allows teams to enforce a limit
+	/// as a security baseline. It is strictly opt-in and cannot be turned off
+	/// again.
+	///
+	/// The check covers the functions on `Socket` as well as [`Dialer::new`]
+	/// and [`Listener::new`]. Endpoints created with [`DialerBuilder`] or
+	/// [`ListenerBuilder`] are checked when they are started, against their
+	/// own `RecvMaxSize`, so that a limit can be given to that endpoint alone.
+	/// Builders created before this function was called are not affected.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{
+	///     options::{Options, RecvMaxSize},
+	///     Error, ListenerBuilder, Protocol, Socket,
+	/// };
+	///
+	/// let s = Socket::new(Protocol::Rep0).unwrap();
+	/// s.require_recv_limit();
+	/// assert_eq!(s.listen("inproc://nng/socket/recv_limit"), Err(Error::IncorrectState));
+	/// assert_eq!(s.dial_async("inproc://nng/socket/recv_limit"), Err(Error::IncorrectState));
+	///
+	/// // A builder is checked against its own limit when it is started.
+	/// let builder = ListenerBuilder::new(&s, "inproc://nng/socket/recv_limit_builder").unwrap();
+	/// builder.set_opt::<RecvMaxSize>(4096).unwrap();
+	/// builder.start().unwrap();
+	///
+	/// s.set_opt::<RecvMaxSize>(64 * 1024).unwrap();
+	/// s.listen("inproc://nng/socket/recv_limit").unwrap();
+	/// ```
+	///
+	/// [`Dialer::new`]: struct.Dialer.html#method.new
+	/// [`DialerBuilder`]: struct.DialerBuilder.html
+	/// [`IncorrectState`]: enum.Error.html#variant.IncorrectState
+	/// [`Listener::new`]: struct.Listener.html#method.new
+	/// [`ListenerBuilder`]: struct.ListenerBuilder.html
+	/// [`RecvMaxSize`]: options/enum.RecvMaxSize.html
+	pub fn require_recv_limit(&self)
+	{
+		self.inner.require_recv_limit.store(true, atomic::Ordering::Relaxed)
+	}
+
+	/// Returns true if `require_recv_limit` has been called on the socket.
+	pub(crate) fn recv_limit_required(&self) -> bool
+	{
+		self.inner.require_recv_limit.load(atomic::Ordering::Relaxed)
+	}
+
+	/// Fails if a receive size limit is required but has not been set.
+	pub(crate) fn check_recv_limit(&self) -> Result<()>
+	{
+		if self.recv_limit_required()
+			&& self.get_opt::<RecvMaxSize>()? == 0
+		{
+			return Err(Error::IncorrectState);
+		}
+
+		Ok(())
+	}
+
 	#[doc(hidden)]
 	#[deprecated(since = "1.0.0-rc.1", note = "Use `TryFrom` instead")]
 	pub fn into_raw(self) -> Option<RawSocket> { RawSocket::try_from(self).ok() }
@@ -474,7 +1314,60 @@ impl Socket
 	/// to wait a brief period after sending data before calling this function.
 	///
 	/// This function will be called automatically when all handles have been
-	/// dropped.
+	/// dropped. Dropping a handle while other clones of the socket are alive
+	/// does not close the socket, while closing it through any handle closes it
+	/// for all of them.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Error, Protocol, Socket};
+	///
+	/// let pull = Socket::new(Protocol::Pull0).unwrap();
+	/// pull.listen("inproc://nng/socket/close").unwrap();
+	///
+	/// let push = Socket::new(Protocol::Push0).unwrap();
+	/// push.dial("inproc://nng/socket/close").unwrap();
+	///
+	/// // Dropping clones leaves the socket open.
+	/// let clones = vec![push.clone(), push.clone()];
+	/// drop(clones);
+	/// push.send(&b"still open"[..]).unwrap();
+	/// assert_eq!(&pull.recv().unwrap()[..], b"still open");
+	///
+	/// // Closing through one handle closes it for every handle.
+	/// let other = push.clone();
+	/// push.close();
+	/// assert_eq!(other.send(&b"closed"[..]).unwrap_err().1, Error::Closed);
+	/// ```
+	///
+	/// ## Teardown
+	///
+	/// Sockets, contexts, and AIO objects may be dropped in any order. NNG
+	/// refers to sockets and contexts by identifier rather than by pointer, so
+	/// a [`Context`] or [`Aio`] that outlives its socket cannot access freed
+	/// memory. Instead, any operation that is still pending when the socket is
+	/// closed completes with [`Closed`], and any further attempt to use a
+	/// context of the closed socket fails with the same error.
+	///
+	/// ```
+	/// use nng::{Aio, AioResult, Error, Protocol, Socket};
+	///
+	/// let socket = Socket::new(Protocol::Pull0).unwrap();
+	/// let (aio, results) = Aio::with_channel().unwrap();
+	/// socket.recv_async(&aio).unwrap();
+	///
+	/// // Dropping the last handle closes the socket and ends the receive.
+	/// drop(socket);
+	/// match results.recv().unwrap() {
+	///     AioResult::Recv(Err(Error::Closed)) => {},
+	///     res => panic!("Unexpected result: {:?}", res),
+	/// }
+	/// ```
+	///
+	/// [`Aio`]: struct.Aio.html
+	/// [`Closed`]: enum.Error.html#variant.Closed
+	/// [`Context`]: struct.Context.html
 	pub fn close(&self) { self.inner.close() }
 
 	/// Returns the underlying `nng_socket`.
@@ -518,6 +1411,275 @@ impl Socket
 	}
 }
 
+#[cfg(feature = "serde")]
+impl Socket
+{
+	/// Serializes the value with the [`Bincode`] codec and sends it on the
+	/// socket.
+	///
+	/// The value is sent in the same manner as [`send`]. If sending fails, the
+	/// serialized message is discarded.
+	///
+	/// # Errors
+	///
+	/// Returns [`CodecError::Codec`] if the value could not be serialized and
+	/// [`CodecError::Nng`] with any error that [`send`] can return.
+	///
+	/// [`Bincode`]: struct.Bincode.html
+	/// [`CodecError::Codec`]: enum.CodecError.html#variant.Codec
+	/// [`CodecError::Nng`]: enum.CodecError.html#variant.Nng
+	/// [`send`]: #method.send
+	pub fn send_value<T>(&self, value: &T) -> std::result::Result<(), CodecError>
+	where
+		T: serde_crate::Serialize + ?Sized,
+	{
+		self.send_value_with::<Bincode, T>(value)
+	}
+
+	/// Serializes the value with the codec `C` and sends it on the socket.
+	///
+	/// This is the same as [`send_value`], but with any [`Codec`] in place of
+	/// [`Bincode`].
+	///
+	/// # Errors
+	///
+	/// Returns [`CodecError::Codec`] if the value could not be serialized and
+	/// [`CodecError::Nng`] with any error that [`send`] can return.
+	///
+	/// [`Bincode`]: struct.Bincode.html
+	/// [`Codec`]: trait.Codec.html
+	/// [`CodecError::Codec`]: enum.CodecError.html#variant.Codec
+	/// [`CodecError::Nng`]: enum.CodecError.html#variant.Nng
+	/// [`send`]: #method.send
+	/// [`send_value`]: #method.send_value
+	pub fn send_value_with<C, T>(&self, value: &T) -> std::result::Result<(), CodecError>
+	where
+		C: Codec,
+		T: serde_crate::Serialize + ?Sized,
+	{
+		let msg = C::encode(value)?;
+		self.send(msg).map_err(|(_, e)| CodecError::Nng(e))
+	}
+
+	/// Receives a message from the socket and deserializes it with the
+	/// [`Bincode`] codec.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Protocol, Socket};
+	///
+	/// let pull = Socket::new(Protocol::Pull0).unwrap();
+	/// pull.listen("inproc://nng/socket/values").unwrap();
+	///
+	/// let push = Socket::new(Protocol::Push0).unwrap();
+	/// push.dial("inproc://nng/socket/values").unwrap();
+	/// push.send_value(&vec![1u16, 2, 3]).unwrap();
+	///
+	/// let value: Vec<u16> = pull.recv_value().unwrap();
+	/// assert_eq!(value, [1, 2, 3]);
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Returns [`CodecError::Nng`] with any error that [`recv`] can return and
+	/// [`CodecError::Codec`] if the message could not be deserialized.
+	///
+	/// [`Bincode`]: struct.Bincode.html
+	/// [`CodecError::Codec`]: enum.CodecError.html#variant.Codec
+	/// [`CodecError::Nng`]: enum.CodecError.html#variant.Nng
+	/// [`recv`]: #method.recv
+	pub fn recv_value<T>(&self) -> std::result::Result<T, CodecError>
+	where
+		T: serde_crate::de::DeserializeOwned,
+	{
+		self.recv_value_with::<Bincode, T>()
+	}
+
+	/// Receives a message from the socket and deserializes it with the codec
+	/// `C`.
+	///
+	/// This is the same as [`recv_value`], but with any [`Codec`] in place of
+	/// [`Bincode`].
+	///
+	/// ## Example
+	///
+	/// ```
+	/// use nng::{Bincode, Protocol, Socket};
+	///
+	/// let pull = Socket::new(Protocol::Pull0).unwrap();
+	/// pull.listen("inproc://nng/socket/values_with").unwrap();
+	///
+	/// let push = Socket::new(Protocol::Push0).unwrap();
+	/// push.dial("inproc://nng/socket/values_with").unwrap();
+	/// push.send_value_with::<Bincode, _>("hello").unwrap();
+	///
+	/// let value: String = pull.recv_value_with::<Bincode, _>().unwrap();
+	/// assert_eq!(value, "hello");
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Returns [`CodecError::Nng`] with any error that [`recv`] can return and
+	/// [`CodecError::Codec`] if the message could not be deserialized.
+	///
+	/// [`Bincode`]: struct.Bincode.html
+	/// [`Codec`]: trait.Codec.html
+	/// [`CodecError::Codec`]: enum.CodecError.html#variant.Codec
+	/// [`CodecError::Nng`]: enum.CodecError.html#variant.Nng
+	/// [`recv`]: #method.recv
+	/// [`recv_value`]: #method.recv_value
+	pub fn recv_value_with<C, T>(&self) -> std::result::Result<T, CodecError>
+	where
+		C: Codec,
+		T: serde_crate::de::DeserializeOwned,
+	{
+		let msg = self.recv()?;
+		C::decode(&msg)
+	}
+}
+
+/// Registers the socket with a `mio` reactor.
+///
+/// The socket is registered through the file descriptors provided by the
+/// [`RecvFd`] and [`SendFd`] options. Interest in [`READABLE`] events registers
+/// the receive descriptor and interest in [`WRITABLE`] events registers the
+/// send descriptor. NNG signals both conditions by making the corresponding
+/// descriptor _readable_, so both are registered with `mio` for readability.
+///
+/// Because both descriptors share the same token, an event does not say which
+/// of the two conditions occurred when both interests are registered. In that
+/// case, the application should attempt both a non-blocking receive and a
+/// non-blocking send when the token becomes ready. Registering a separate
+/// clone of the socket per direction avoids this ambiguity as long as each
+/// clone is given its own token.
+///
+/// Protocols that only send or only receive, such as _pub_ and _sub_, do not
+/// provide the descriptor for the other direction. Interest in that direction
+/// is ignored, so registering a _sub_ socket for [`WRITABLE`] events succeeds
+/// but never produces an event.
+///
+/// The events are level-triggered on the NNG side but `mio` uses edge-triggered
+/// notifications, so the application must drain the socket (until an operation
+/// fails with [`TryAgain`]) before waiting for the next event.
+///
+/// [`READABLE`]: https://docs.rs/mio/0.7/mio/struct.Interest.html#associatedconstant.READABLE
+/// [`RecvFd`]: options/enum.RecvFd.html
+/// [`SendFd`]: options/enum.SendFd.html
+/// [`TryAgain`]: enum.Error.html#variant.TryAgain
+/// [`WRITABLE`]: https://docs.rs/mio/0.7/mio/struct.Interest.html#associatedconstant.WRITABLE
+#[cfg(all(feature = "mio", unix))]
+impl mio::event::Source for Socket
+{
+	fn register(
+		&mut self,
+		registry: &mio::Registry,
+		token: mio::Token,
+		interests: mio::Interest,
+	) -> io::Result<()>
+	{
+		for fd in self.poll_fds(interests)?.iter().flatten() {
+			mio::unix::SourceFd(fd).register(registry, token, mio::Interest::READABLE)?;
+		}
+
+		Ok(())
+	}
+
+	fn reregister(
+		&mut self,
+		registry: &mio::Registry,
+		token: mio::Token,
+		interests: mio::Interest,
+	) -> io::Result<()>
+	{
+		// The set of descriptors may change between registrations, so any that are
+		// no longer wanted are removed and any that are newly wanted are added.
+		let wanted = self.poll_fds(interests)?;
+		let all = self.poll_fds(mio::Interest::READABLE | mio::Interest::WRITABLE)?;
+
+		for (fd, wanted) in all.iter().zip(wanted.iter()) {
+			let fd = match fd {
+				Some(fd) => *fd,
+				None => continue,
+			};
+			let mut source = mio::unix::SourceFd(&fd);
+
+			if wanted.is_some() {
+				match source.reregister(registry, token, mio::Interest::READABLE) {
+					Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+						source.register(registry, token, mio::Interest::READABLE)?
+					},
+					res => res?,
+				}
+			}
+			else {
+				ignore_not_found(source.deregister(registry))?;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn deregister(&mut self, registry: &mio::Registry) -> io::Result<()>
+	{
+		let all = self.poll_fds(mio::Interest::READABLE | mio::Interest::WRITABLE)?;
+		for fd in all.iter().flatten() {
+			ignore_not_found(mio::unix::SourceFd(fd).deregister(registry))?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(all(feature = "mio", unix))]
+impl Socket
+{
+	/// Returns the receive and send descriptors that match the interests.
+	///
+	/// A descriptor the protocol does not provide, such as the send descriptor
+	/// of a _sub_ socket, is returned as `None`.
+	fn poll_fds(&self, interests: mio::Interest) -> io::Result<[Option<RawFd>; 2]>
+	{
+		use crate::options::{RecvFd, SendFd};
+
+		let recv = if interests.is_readable() {
+			fd_or_none(self.get_opt::<RecvFd>())?
+		}
+		else {
+			None
+		};
+		let send = if interests.is_writable() {
+			fd_or_none(self.get_opt::<SendFd>())?
+		}
+		else {
+			None
+		};
+
+		Ok([recv, send])
+	}
+}
+
+/// Treats a descriptor that the protocol does not support as missing.
+#[cfg(all(feature = "mio", unix))]
+fn fd_or_none(res: Result<RawFd>) -> io::Result<Option<RawFd>>
+{
+	match res {
+		Ok(fd) => Ok(Some(fd)),
+		Err(Error::NotSupported) => Ok(None),
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// Treats an error caused by a descriptor not being registered as success.
+#[cfg(all(feature = "mio", unix))]
+fn ignore_not_found(res: io::Result<()>) -> io::Result<()>
+{
+	match res {
+		Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+		res => res,
+	}
+}
+
 #[cfg(feature = "ffi-module")]
 impl Socket
 {
@@ -586,6 +1748,8 @@ expose_options!{
 	Gets -> [Raw, MaxTtl, RecvBufferSize,
 	         RecvTimeout, SendBufferSize,
 	         SendTimeout, SocketName,
+	         ProtocolId, ProtocolName,
+	         PeerId, PeerName,
 	         protocol::pair::Polyamorous,
 	         protocol::reqrep::ResendTime,
 	         protocol::survey::SurveyTime];
@@ -616,6 +1780,32 @@ mod unix_impls
 	impl GetOpt<SendFd> for Socket {}
 }
 
+/// An iterator over the messages received on a [`Socket`].
+///
+/// This type is created by the [`Socket::incoming`] function.
+///
+/// [`Socket`]: struct.Socket.html
+/// [`Socket::incoming`]: struct.Socket.html#method.incoming
+#[derive(Debug)]
+pub struct Incoming<'a>
+{
+	/// The socket from which messages are received.
+	socket: &'a Socket,
+}
+
+impl<'a> Iterator for Incoming<'a>
+{
+	type Item = Result<Message>;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		match self.socket.recv() {
+			Err(Error::Closed) => None,
+			res => Some(res),
+		}
+	}
+}
+
 /// A wrapper type around the underlying `nng_socket`.
 ///
 /// This allows us to have mutliple Rust socket types that won't clone the C
@@ -627,9 +1817,18 @@ struct Inner
 
 	/// The current pipe event callback.
 	pipe_notify: RwLock<Option<Box<PipeNotifyFn>>>,
+
+	/// Whether dialing or listening requires a receive size limit.
+	require_recv_limit: AtomicBool,
 }
 impl Inner
 {
+	/// Creates the shared state for a newly opened socket.
+	fn new(handle: nng_sys::nng_socket) -> Self
+	{
+		Inner { handle, pipe_notify: RwLock::new(None), require_recv_limit: AtomicBool::new(false) }
+	}
+
 	fn close(&self)
 	{
 		// Closing a socket should only ever return success or ECLOSED and both
@@ -652,6 +1851,7 @@ impl fmt::Debug for Inner
 		f.debug_struct("Inner")
 			.field("handle", &self.handle)
 			.field("pipe_notify", &self.pipe_notify.read().unwrap().is_some())
+			.field("require_recv_limit", &self.require_recv_limit.load(atomic::Ordering::Relaxed))
 			.finish()
 	}
 }
@@ -722,8 +1922,7 @@ impl RawSocket
 			return Err(Error::from(e));
 		}
 
-		let socket =
-			Socket { inner: Arc::new(Inner { handle: socket, pipe_notify: RwLock::new(None) }) };
+		let socket = Socket { inner: Arc::new(Inner::new(socket)) };
 
 		Ok(RawSocket { socket, _hidden: () })
 	}
@@ -735,7 +1934,7 @@ impl TryFrom<Socket> for RawSocket
 
 	fn try_from(socket: Socket) -> std::result::Result<Self, Self::Error>
 	{
-		use crate::options::{Options, Raw};
+		use crate::options::Raw;
 
 		if socket.get_opt::<Raw>().expect("Socket should have \"raw\" option available") {
 			Ok(RawSocket { socket, _hidden: () })
@@ -762,3 +1961,155 @@ impl error::Error for CookedSocketError
 {
 	fn description(&self) -> &str { "Socket is in \"cooked\" (not \"raw\") mode" }
 }
+
+/// Represents the type of a deferred option setter.
+type OptSetter = Box<dyn FnOnce(&Socket) -> Result<()> + Send>;
+
+/// A builder for sockets that are configured before they are handed out.
+///
+/// The builder records the protocol of the socket and any number of options.
+/// When the socket is built, the options are applied in the order in which
+/// they were added and building stops at the first option that fails. The
+/// same builder can produce either a regular [`Socket`] or a [`RawSocket`].
+///
+/// ## Example
+///
+/// ```
+/// use std::time::Duration;
+/// use nng::{
+///     options::{RecvMaxSize, RecvTimeout, SendBufferSize, SocketName},
+///     Protocol, SocketBuilder,
+/// };
+///
+/// let socket = SocketBuilder::new(Protocol::Rep0)
+///     .with_opt::<RecvTimeout>(Some(Duration::from_secs(1)))
+///     .with_opt::<SendBufferSize>(16)
+///     .with_opt::<RecvMaxSize>(1024 * 1024)
+///     .with_opt::<SocketName>("server".to_string())
+///     .build()
+///     .unwrap();
+///
+/// socket.listen("inproc://nng/socket/builder").unwrap();
+/// ```
+///
+/// [`RawSocket`]: struct.RawSocket.html
+/// [`Socket`]: struct.Socket.html
+pub struct SocketBuilder
+{
+	/// The protocol of the socket.
+	protocol: Protocol,
+
+	/// The options to apply, in order.
+	opts: Vec<OptSetter>,
+}
+impl SocketBuilder
+{
+	/// Creates a new builder for a socket of the specified protocol.
+	pub fn new(protocol: Protocol) -> Self { SocketBuilder { protocol, opts: Vec::new() } }
+
+	/// Adds an option to apply to the socket once it has been created.
+	pub fn with_opt<T>(mut self, val: T::OptType) -> Self
+	where
+		T: OptOps + 'static,
+		T::OptType: Send + 'static,
+		Socket: SetOpt<T>,
+	{
+		self.opts.push(Box::new(move |s: &Socket| s.set_opt::<T>(val)));
+		self
+	}
+
+	/// Creates the socket in "cooked" mode and applies the options.
+	///
+	/// # Errors
+	///
+	/// Any error that [`Socket::new`] can return, or the first error returned
+	/// while setting an option.
+	///
+	/// [`Socket::new`]: struct.Socket.html#method.new
+	pub fn build(self) -> Result<Socket>
+	{
+		let socket = Socket::new(self.protocol)?;
+		SocketBuilder::apply(&socket, self.opts)?;
+
+		Ok(socket)
+	}
+
+	/// Creates the socket in "raw" mode and applies the options.
+	///
+	/// # Errors
+	///
+	/// Any error that [`RawSocket::new`] can return, or the first error
+	/// returned while setting an option.
+	///
+	/// [`RawSocket::new`]: struct.RawSocket.html#method.new
+	pub fn build_raw(self) -> Result<RawSocket>
+	{
+		let raw = RawSocket::new(self.protocol)?;
+		SocketBuilder::apply(&raw.socket, self.opts)?;
+
+		Ok(raw)
+	}
+
+	/// Applies the options to the socket in order.
+	fn apply(socket: &Socket, opts: Vec<OptSetter>) -> Result<()>
+	{
+		opts.into_iter().map(|set| set(socket)).collect()
+	}
+}
+
+impl fmt::Debug for SocketBuilder
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		f.debug_struct("SocketBuilder")
+			.field("protocol", &self.protocol)
+			.field("opts", &self.opts.len())
+			.finish()
+	}
+}
+
+#[cfg(all(test, feature = "mio", unix))]
+mod tests
+{
+	use super::*;
+	use mio::{Events, Interest, Poll, Token};
+
+	#[test]
+	fn mio_one_way_protocols()
+	{
+		for &protocol in &[Protocol::Sub0, Protocol::Push0] {
+			let mut poll = Poll::new().unwrap();
+			let mut socket = Socket::new(protocol).unwrap();
+			let both = Interest::READABLE | Interest::WRITABLE;
+
+			poll.registry().register(&mut socket, Token(0), both).unwrap();
+			poll.registry().reregister(&mut socket, Token(0), Interest::READABLE).unwrap();
+			poll.registry().reregister(&mut socket, Token(0), Interest::WRITABLE).unwrap();
+			poll.registry().reregister(&mut socket, Token(0), both).unwrap();
+			poll.registry().deregister(&mut socket).unwrap();
+		}
+	}
+
+	#[test]
+	fn mio_readable_event()
+	{
+		const URL: &str = "inproc://nng-rs/mio_readable_event";
+
+		let mut rx = Socket::new(Protocol::Pair0).unwrap();
+		rx.listen(URL).unwrap();
+		let tx = Socket::new(Protocol::Pair0).unwrap();
+		tx.dial(URL).unwrap();
+
+		let mut poll = Poll::new().unwrap();
+		poll.registry().register(&mut rx, Token(1), Interest::READABLE).unwrap();
+
+		tx.send(&b"ping"[..]).unwrap();
+
+		let mut events = Events::with_capacity(4);
+		poll.poll(&mut events, Some(Duration::from_secs(5))).unwrap();
+		assert!(events.iter().any(|e| e.token() == Token(1)));
+		assert_eq!(&rx.try_recv().unwrap()[..], b"ping");
+
+		poll.registry().deregister(&mut rx).unwrap();
+	}
+}
@@ -0,0 +1,236 @@
+use std::{borrow::Cow, ffi::CStr, fmt, marker::PhantomData, os::raw::c_char, ptr};
+
+use crate::{error::Result, util::validate_ptr};
+
+/// Takes a snapshot of all of the statistics maintained by NNG.
+///
+/// The statistics are organized as a tree, rooted at a scope with no name.
+/// Beneath the root are scopes for each socket, dialer, listener, and pipe,
+/// which in turn contain the individual statistics for those objects. The
+/// snapshot is not updated after it has been taken; in order to see new
+/// values, a new snapshot must be taken.
+///
+/// See the [NNG documentation][1] for more information.
+///
+/// # Errors
+///
+/// * [`NotSupported`]: Statistics are not supported by the library.
+/// * [`OutOfMemory`]: Insufficient memory available.
+///
+/// # Example
+///
+/// ```
+/// use nng::{Protocol, Socket, StatValue};
+///
+/// let _socket = Socket::new(Protocol::Pair0).unwrap();
+/// let stats = nng::stats().unwrap();
+///
+/// for scope in stats.root().children() {
+///     for stat in scope.children() {
+///         if let StatValue::Counter(count) = stat.value() {
+///             println!("{}.{}: {}", scope.name(), stat.name(), count);
+///         }
+///     }
+/// }
+/// ```
+///
+/// [1]: https://nanomsg.github.io/nng/man/v1.2.2/nng_stats_get.3.html
+/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+pub fn stats() -> Result<Stats>
+{
+	let mut root: *mut nng_sys::nng_stat = ptr::null_mut();
+	let rv = unsafe { nng_sys::nng_stats_get(&mut root as *mut _) };
+
+	validate_ptr(rv, root).map(|root| Stats { root: root.as_ptr() })
+}
+
+/// A snapshot of the NNG statistics tree.
+///
+/// The snapshot owns all of the statistics within it and releases them when
+/// dropped. Individual nodes of the tree are accessed through the [`root`]
+/// function.
+///
+/// [`root`]: #method.root
+pub struct Stats
+{
+	/// The root of the statistics tree returned by `nng_stats_get`.
+	root: *mut nng_sys::nng_stat,
+}
+impl Stats
+{
+	/// Returns the root scope of the statistics tree.
+	pub fn root(&self) -> Stat
+	{
+		Stat { stat: self.root, _snapshot: PhantomData }
+	}
+}
+
+impl fmt::Debug for Stats
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		f.debug_struct("Stats").field("root", &self.root()).finish()
+	}
+}
+
+impl Drop for Stats
+{
+	fn drop(&mut self)
+	{
+		unsafe { nng_sys::nng_stats_free(self.root) }
+	}
+}
+
+/// A single node within a [`Stats`] snapshot.
+///
+/// [`Stats`]: struct.Stats.html
+#[derive(Clone, Copy)]
+pub struct Stat<'a>
+{
+	/// The underlying NNG statistic.
+	stat: *mut nng_sys::nng_stat,
+
+	/// Ties the lifetime of the node to the snapshot that owns it.
+	_snapshot: PhantomData<&'a Stats>,
+}
+impl<'a> Stat<'a>
+{
+	/// Returns the name of the statistic.
+	///
+	/// Names are not unique within the tree. For example, every socket scope
+	/// is named `socket`.
+	pub fn name(self) -> Cow<'a, str>
+	{
+		unsafe { str_from_ptr(nng_sys::nng_stat_name(self.stat)) }
+	}
+
+	/// Returns a human-readable description of the statistic.
+	pub fn desc(self) -> Cow<'a, str>
+	{
+		unsafe { str_from_ptr(nng_sys::nng_stat_desc(self.stat)) }
+	}
+
+	/// Returns the value of the statistic.
+	pub fn value(self) -> StatValue<'a>
+	{
+		use nng_sys::nng_stat_type_enum::*;
+
+		let ty = unsafe { nng_sys::nng_stat_type(self.stat) };
+		match nng_sys::nng_stat_type_enum::try_convert_from(ty) {
+			Some(NNG_STAT_SCOPE) => StatValue::Scope,
+			Some(NNG_STAT_LEVEL) => StatValue::Level(self.raw_value()),
+			Some(NNG_STAT_COUNTER) => StatValue::Counter(self.raw_value()),
+			Some(NNG_STAT_STRING) => {
+				StatValue::String(unsafe { str_from_ptr(nng_sys::nng_stat_string(self.stat)) })
+			},
+			Some(NNG_STAT_BOOLEAN) => StatValue::Boolean(self.raw_value() != 0),
+			Some(NNG_STAT_ID) => StatValue::Id(self.raw_value()),
+			None => StatValue::Unknown,
+		}
+	}
+
+	/// Returns the time, in milliseconds, at which the statistic was captured.
+	///
+	/// The value is relative to an arbitrary point in time and is only useful
+	/// for comparing against the timestamps of other statistics.
+	pub fn timestamp(self) -> u64 { unsafe { nng_sys::nng_stat_timestamp(self.stat) } }
+
+	/// Returns an iterator over the children of this statistic.
+	///
+	/// Only scopes have children. For all other statistics, the iterator will
+	/// be empty.
+	pub fn children(self) -> StatChildren<'a>
+	{
+		let next = unsafe { nng_sys::nng_stat_child(self.stat) };
+		StatChildren { next, _snapshot: PhantomData }
+	}
+
+	/// Returns the raw numeric value of the statistic.
+	fn raw_value(self) -> u64 { unsafe { nng_sys::nng_stat_value(self.stat) } }
+}
+
+impl<'a> fmt::Debug for Stat<'a>
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		f.debug_struct("Stat")
+			.field("name", &self.name())
+			.field("value", &self.value())
+			.field("children", &self.children().count())
+			.finish()
+	}
+}
+
+/// The value held by a statistic.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum StatValue<'a>
+{
+	/// The statistic is a scope that only contains other statistics.
+	Scope,
+
+	/// A value that can go up or down, such as the number of open pipes.
+	Level(u64),
+
+	/// A value that only increases, such as the number of messages sent.
+	Counter(u64),
+
+	/// A string value, such as the URL of a listener.
+	String(Cow<'a, str>),
+
+	/// A boolean value.
+	Boolean(bool),
+
+	/// The numeric identifier of an object, such as a socket.
+	Id(u64),
+
+	/// The statistic is of a type not known to this library.
+	Unknown,
+}
+
+/// An iterator over the children of a [`Stat`].
+///
+/// [`Stat`]: struct.Stat.html
+#[derive(Clone)]
+pub struct StatChildren<'a>
+{
+	/// The next child to yield, or null if there are no more children.
+	next: *mut nng_sys::nng_stat,
+
+	/// Ties the lifetime of the iterator to the snapshot that owns it.
+	_snapshot: PhantomData<&'a Stats>,
+}
+
+impl<'a> Iterator for StatChildren<'a>
+{
+	type Item = Stat<'a>;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		if self.next.is_null() {
+			return None;
+		}
+
+		let stat = Stat { stat: self.next, _snapshot: PhantomData };
+		self.next = unsafe { nng_sys::nng_stat_next(self.next) };
+
+		Some(stat)
+	}
+}
+
+impl<'a> fmt::Debug for StatChildren<'a>
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		f.debug_list().entries(self.clone()).finish()
+	}
+}
+
+/// Creates a string from a pointer owned by a statistics snapshot.
+///
+/// The function is unsafe because the pointer must be either null or a valid,
+/// null terminated string that lives at least as long as `'a`.
+unsafe fn str_from_ptr<'a>(ptr: *const c_char) -> Cow<'a, str>
+{
+	if ptr.is_null() { Cow::Borrowed("") } else { CStr::from_ptr(ptr).to_string_lossy() }
+}
@@ -0,0 +1,100 @@
+use std::io::{self, Read, Write};
+
+use crate::{error::Error, message::Message, socket::Socket};
+
+/// Adapts a socket into a byte stream.
+///
+/// Reading from the stream receives messages from the socket and hands out
+/// their contents, buffering any bytes that did not fit in the caller's buffer
+/// until the next read. Message boundaries are not preserved. Each call to
+/// `write` sends the provided bytes as a single message.
+///
+/// Reading reports the end of the stream once the socket has been closed.
+/// Empty messages are skipped so that they are not mistaken for the end of
+/// the stream.
+///
+/// This is mostly useful for protocols such as _pair_ or _pipeline_, where
+/// there is a single logical stream of messages in each direction.
+///
+/// ## Example
+///
+/// ```
+/// use std::io::{Read, Write};
+/// use nng::{Protocol, Socket, SocketStream};
+///
+/// let pull = Socket::new(Protocol::Pull0).unwrap();
+/// pull.listen("inproc://nng/stream/example").unwrap();
+///
+/// let push = Socket::new(Protocol::Push0).unwrap();
+/// push.dial("inproc://nng/stream/example").unwrap();
+///
+/// let mut writer = SocketStream::new(push);
+/// writer.write_all(b"hello").unwrap();
+/// writer.write_all(b" world").unwrap();
+///
+/// let mut reader = SocketStream::new(pull);
+/// let mut buf = [0u8; 11];
+/// reader.read_exact(&mut buf).unwrap();
+/// assert_eq!(&buf, b"hello world");
+/// ```
+#[derive(Debug)]
+pub struct SocketStream
+{
+	/// The socket used to send and receive messages.
+	socket: Socket,
+
+	/// The remaining contents of the last message that was received.
+	buffer: Option<Message>,
+}
+impl SocketStream
+{
+	/// Creates a new stream on top of the provided socket.
+	pub const fn new(socket: Socket) -> Self { SocketStream { socket, buffer: None } }
+
+	/// Returns a reference to the underlying socket.
+	pub const fn socket(&self) -> &Socket { &self.socket }
+
+	/// Consumes the stream and returns the underlying socket.
+	///
+	/// Any buffered bytes that have not yet been read are lost.
+	pub fn into_inner(self) -> Socket { self.socket }
+}
+
+impl Read for SocketStream
+{
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>
+	{
+		let mut msg = match self.buffer.take() {
+			Some(msg) => msg,
+			None => loop {
+				match self.socket.recv() {
+					Ok(msg) if msg.is_empty() => continue,
+					Ok(msg) => break msg,
+					Err(Error::Closed) => return Ok(0),
+					Err(e) => return Err(e.into()),
+				}
+			},
+		};
+
+		let len = buf.len().min(msg.len());
+		buf[..len].copy_from_slice(&msg[..len]);
+		msg.trim(len);
+
+		if !msg.is_empty() {
+			self.buffer = Some(msg);
+		}
+
+		Ok(len)
+	}
+}
+
+impl Write for SocketStream
+{
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize>
+	{
+		self.socket.send(buf).map_err(|(_, e)| io::Error::from(e))?;
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
@@ -0,0 +1,55 @@
+//! Helpers for writing tests against this crate.
+//!
+//! This module is only available when the `test-util` feature is enabled and
+//! is intended for `dev-dependencies`.
+use std::{
+	process,
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{error::Result, protocol::Protocol, socket::Socket};
+
+/// Counter used to give every loopback pair its own address.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Creates a pair of sockets connected over a unique `inproc` address.
+///
+/// The first protocol of the pair is used for the listening socket and the
+/// second for the dialing socket, which are returned in that order. Every call
+/// uses a different address, so tests running in parallel cannot collide.
+/// Since `inproc` connections are established synchronously, both sockets are
+/// connected by the time this function returns.
+///
+/// # Errors
+///
+/// Any error that [`Socket::new`], [`Socket::listen`], or [`Socket::dial`]
+/// can return.
+///
+/// ## Example
+///
+/// ```
+/// use nng::{testutil, Protocol};
+///
+/// let (server, client) = testutil::loopback((Protocol::Rep0, Protocol::Req0)).unwrap();
+///
+/// client.send(&b"ping"[..]).unwrap();
+/// let msg = server.recv().unwrap();
+/// assert_eq!(&msg[..], b"ping");
+/// ```
+///
+/// [`Socket::dial`]: ../struct.Socket.html#method.dial
+/// [`Socket::listen`]: ../struct.Socket.html#method.listen
+/// [`Socket::new`]: ../struct.Socket.html#method.new
+pub fn loopback(protocols: (Protocol, Protocol)) -> Result<(Socket, Socket)>
+{
+	let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+	let url = format!("inproc://nng-rs/testutil/loopback/{}/{}", process::id(), id);
+
+	let listener = Socket::new(protocols.0)?;
+	listener.listen(&url)?;
+
+	let dialer = Socket::new(protocols.1)?;
+	dialer.dial(&url)?;
+
+	Ok((listener, dialer))
+}
@@ -0,0 +1,297 @@
+use std::{
+	ffi::CString,
+	fmt,
+	os::raw::c_char,
+	ptr::{self, NonNull},
+};
+
+use crate::{
+	error::{Error, Result},
+	util::validate_ptr,
+};
+
+/// A TLS configuration object.
+///
+/// The configuration is used to set up the TLS layer of the `tls+tcp://` and
+/// `wss://` transports. Once constructed, it is attached to a dialer or
+/// listener through the [`transport::tls::Config`] option.
+///
+/// The underlying configuration is reference counted, so cloning this type is
+/// cheap and all clones refer to the same configuration. Once a configuration
+/// has been attached to a dialer or listener it becomes read-only and further
+/// attempts to modify it will fail with [`Busy`].
+///
+/// For mutual authentication, set the authentication mode to
+/// [`TlsAuthMode::Required`] on both sides. Whether a connected peer was
+/// actually verified can be checked on its pipe with the
+/// [`transport::tls::Verified`] option.
+///
+/// See the [NNG documentation][1] for more information.
+///
+/// ## Example
+///
+/// ```no_run
+/// use nng::{
+///     options::{transport::tls, Options},
+///     DialerBuilder, Protocol, Socket, TlsAuthMode, TlsConfig, TlsMode,
+/// };
+///
+/// let config = TlsConfig::new(TlsMode::Client).unwrap();
+/// config.set_ca_file("ca.pem").unwrap();
+/// config.set_server_name("example.com").unwrap();
+/// config.set_auth_mode(TlsAuthMode::Required).unwrap();
+///
+/// let socket = Socket::new(Protocol::Req0).unwrap();
+/// let builder = DialerBuilder::new(&socket, "tls+tcp://example.com:5555").unwrap();
+/// builder.set_opt::<tls::Config>(config).unwrap();
+/// let _dialer = builder.start(false).unwrap();
+/// ```
+///
+/// [1]: https://nanomsg.github.io/nng/man/v1.2.2/nng_tls_config_alloc.3tls.html
+/// [`Busy`]: enum.Error.html#variant.Busy
+/// [`TlsAuthMode::Required`]: enum.TlsAuthMode.html#variant.Required
+/// [`transport::tls::Config`]: options/transport/tls/enum.Config.html
+/// [`transport::tls::Verified`]: options/transport/tls/enum.Verified.html
+pub struct TlsConfig
+{
+	/// The underlying NNG configuration.
+	config: NonNull<nng_sys::nng_tls_config>,
+}
+impl TlsConfig
+{
+	/// Creates a new TLS configuration for the specified mode.
+	///
+	/// # Errors
+	///
+	/// * [`NotSupported`]: TLS support is not available.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// [`NotSupported`]: enum.Error.html#variant.NotSupported
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	pub fn new(mode: TlsMode) -> Result<TlsConfig>
+	{
+		let mode = match mode {
+			TlsMode::Client => nng_sys::nng_tls_mode::NNG_TLS_MODE_CLIENT,
+			TlsMode::Server => nng_sys::nng_tls_mode::NNG_TLS_MODE_SERVER,
+		};
+
+		let mut config: *mut nng_sys::nng_tls_config = ptr::null_mut();
+		let rv = unsafe { nng_sys::nng_tls_config_alloc(&mut config as *mut _, mode) };
+
+		validate_ptr(rv, config).map(|config| TlsConfig { config })
+	}
+
+	/// Sets the certificate authority chain used to validate the peer.
+	///
+	/// Both the chain and the optional certificate revocation list must be
+	/// PEM encoded.
+	///
+	/// # Errors
+	///
+	/// * [`Busy`]: The configuration is already in use.
+	/// * [`InvalidInput`]: The chain or revocation list is invalid.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// [`Busy`]: enum.Error.html#variant.Busy
+	/// [`InvalidInput`]: enum.Error.html#variant.InvalidInput
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	pub fn set_ca_chain(&self, chain: &str, crl: Option<&str>) -> Result<()>
+	{
+		let chain = CString::new(chain).map_err(|_| Error::InvalidInput)?;
+		let crl = crl.map(CString::new).transpose().map_err(|_| Error::InvalidInput)?;
+
+		let rv = unsafe {
+			nng_sys::nng_tls_config_ca_chain(self.config.as_ptr(), chain.as_ptr(), opt_ptr(&crl))
+		};
+
+		rv2res!(rv)
+	}
+
+	/// Loads the certificate authority chain from a PEM encoded file.
+	///
+	/// The file may also contain certificate revocation lists.
+	///
+	/// # Errors
+	///
+	/// * [`Busy`]: The configuration is already in use.
+	/// * [`InvalidInput`]: The file contents are invalid.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// [`Busy`]: enum.Error.html#variant.Busy
+	/// [`InvalidInput`]: enum.Error.html#variant.InvalidInput
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	pub fn set_ca_file(&self, path: &str) -> Result<()>
+	{
+		let path = CString::new(path).map_err(|_| Error::InvalidInput)?;
+		let rv = unsafe { nng_sys::nng_tls_config_ca_file(self.config.as_ptr(), path.as_ptr()) };
+
+		rv2res!(rv)
+	}
+
+	/// Sets the certificate and private key used to identify this peer.
+	///
+	/// Both the certificate and the key must be PEM encoded. If the key is
+	/// encrypted, the password used to decrypt it must be provided.
+	///
+	/// # Errors
+	///
+	/// * [`Busy`]: The configuration is already in use.
+	/// * [`InvalidInput`]: The certificate or key is invalid.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// [`Busy`]: enum.Error.html#variant.Busy
+	/// [`InvalidInput`]: enum.Error.html#variant.InvalidInput
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	pub fn set_own_cert(&self, cert: &str, key: &str, password: Option<&str>) -> Result<()>
+	{
+		let cert = CString::new(cert).map_err(|_| Error::InvalidInput)?;
+		let key = CString::new(key).map_err(|_| Error::InvalidInput)?;
+		let pass = password.map(CString::new).transpose().map_err(|_| Error::InvalidInput)?;
+
+		let rv = unsafe {
+			nng_sys::nng_tls_config_own_cert(
+				self.config.as_ptr(),
+				cert.as_ptr(),
+				key.as_ptr(),
+				opt_ptr(&pass),
+			)
+		};
+
+		rv2res!(rv)
+	}
+
+	/// Loads the certificate and private key used to identify this peer from
+	/// a single PEM encoded file.
+	///
+	/// # Errors
+	///
+	/// * [`Busy`]: The configuration is already in use.
+	/// * [`InvalidInput`]: The file contents are invalid.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// [`Busy`]: enum.Error.html#variant.Busy
+	/// [`InvalidInput`]: enum.Error.html#variant.InvalidInput
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	pub fn set_cert_key_file(&self, path: &str, password: Option<&str>) -> Result<()>
+	{
+		let path = CString::new(path).map_err(|_| Error::InvalidInput)?;
+		let pass = password.map(CString::new).transpose().map_err(|_| Error::InvalidInput)?;
+
+		let rv = unsafe {
+			nng_sys::nng_tls_config_cert_key_file(
+				self.config.as_ptr(),
+				path.as_ptr(),
+				opt_ptr(&pass),
+			)
+		};
+
+		rv2res!(rv)
+	}
+
+	/// Sets the authentication mode used to verify the remote peer.
+	///
+	/// By default, clients require the server to be verified while servers do
+	/// not authenticate clients.
+	///
+	/// # Errors
+	///
+	/// * [`Busy`]: The configuration is already in use.
+	///
+	/// [`Busy`]: enum.Error.html#variant.Busy
+	pub fn set_auth_mode(&self, mode: TlsAuthMode) -> Result<()>
+	{
+		let mode = match mode {
+			TlsAuthMode::None => nng_sys::nng_tls_auth_mode::NNG_TLS_AUTH_MODE_NONE,
+			TlsAuthMode::Optional => nng_sys::nng_tls_auth_mode::NNG_TLS_AUTH_MODE_OPTIONAL,
+			TlsAuthMode::Required => nng_sys::nng_tls_auth_mode::NNG_TLS_AUTH_MODE_REQUIRED,
+		};
+
+		let rv = unsafe { nng_sys::nng_tls_config_auth_mode(self.config.as_ptr(), mode) };
+		rv2res!(rv)
+	}
+
+	/// Sets the name of the server.
+	///
+	/// This is used by clients to verify the server certificate and is sent to
+	/// the server using the SNI extension.
+	///
+	/// # Errors
+	///
+	/// * [`Busy`]: The configuration is already in use.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// [`Busy`]: enum.Error.html#variant.Busy
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	pub fn set_server_name(&self, name: &str) -> Result<()>
+	{
+		let name = CString::new(name).map_err(|_| Error::InvalidInput)?;
+		let rv =
+			unsafe { nng_sys::nng_tls_config_server_name(self.config.as_ptr(), name.as_ptr()) };
+
+		rv2res!(rv)
+	}
+
+	/// Returns the underlying `nng_tls_config`.
+	pub(crate) const fn as_ptr(&self) -> *mut nng_sys::nng_tls_config { self.config.as_ptr() }
+}
+
+impl Clone for TlsConfig
+{
+	fn clone(&self) -> Self
+	{
+		unsafe { nng_sys::nng_tls_config_hold(self.config.as_ptr()) }
+		TlsConfig { config: self.config }
+	}
+}
+
+impl fmt::Debug for TlsConfig
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		f.debug_struct("TlsConfig").field("config", &self.config).finish()
+	}
+}
+
+impl Drop for TlsConfig
+{
+	fn drop(&mut self)
+	{
+		// This only decrements the reference count.
+		unsafe { nng_sys::nng_tls_config_free(self.config.as_ptr()) }
+	}
+}
+
+// NNG protects the configuration with its own lock.
+unsafe impl Send for TlsConfig {}
+unsafe impl Sync for TlsConfig {}
+
+/// The role a [`TlsConfig`] plays in the TLS session.
+///
+/// [`TlsConfig`]: struct.TlsConfig.html
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum TlsMode
+{
+	/// The configuration is used by a client, typically a dialer.
+	Client,
+
+	/// The configuration is used by a server, typically a listener.
+	Server,
+}
+
+/// How the remote peer is authenticated.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum TlsAuthMode
+{
+	/// No authentication of the peer is performed.
+	None,
+
+	/// The peer is verified if it presents a certificate, but the session is
+	/// still established if it does not.
+	Optional,
+
+	/// The peer must present a valid certificate.
+	Required,
+}
+
+/// Returns a pointer to the optional string, or null if it is not present.
+fn opt_ptr(s: &Option<CString>) -> *const c_char { s.as_ref().map_or(ptr::null(), |s| s.as_ptr()) }
@@ -0,0 +1,177 @@
+use std::{
+	ffi::{CStr, CString},
+	fmt,
+	os::raw::c_char,
+	ptr::{self, NonNull},
+	str::FromStr,
+};
+
+use crate::{
+	error::{Error, Result},
+	util::validate_ptr,
+};
+
+/// A URL as understood by NNG.
+///
+/// This type can be used to validate and inspect an address before it is
+/// handed to a dialer or listener. Note that successfully parsing a URL does
+/// not mean that the scheme is backed by an available transport.
+///
+/// See the [NNG documentation][1] for more information.
+///
+/// ## Example
+///
+/// ```
+/// use nng::Url;
+///
+/// let url = Url::parse("tcp://user@localhost:5555/path?query").unwrap();
+/// assert_eq!(url.scheme(), "tcp");
+/// assert_eq!(url.hostname(), "localhost");
+/// assert_eq!(url.port(), Some(5555));
+/// assert_eq!(url.path(), "/path");
+/// assert_eq!(url.query(), Some("query"));
+/// assert_eq!(url.userinfo(), Some("user"));
+/// ```
+///
+/// [1]: https://nanomsg.github.io/nng/man/v1.2.2/nng_url.5.html
+pub struct Url
+{
+	/// The parsed NNG URL.
+	url: NonNull<nng_sys::nng_url>,
+}
+impl Url
+{
+	/// Parses the provided string as a URL.
+	///
+	/// # Errors
+	///
+	/// * [`AddressInvalid`]: The string is not a valid URL.
+	/// * [`OutOfMemory`]: Insufficient memory available.
+	///
+	/// [`AddressInvalid`]: enum.Error.html#variant.AddressInvalid
+	/// [`OutOfMemory`]: enum.Error.html#variant.OutOfMemory
+	pub fn parse(url: &str) -> Result<Url>
+	{
+		let raw = CString::new(url).map_err(|_| Error::AddressInvalid)?;
+
+		let mut url: *mut nng_sys::nng_url = ptr::null_mut();
+		let rv = unsafe { nng_sys::nng_url_parse(&mut url as *mut _, raw.as_ptr()) };
+
+		match validate_ptr(rv, url) {
+			Ok(url) => Ok(Url { url }),
+			Err(Error::OutOfMemory) => Err(Error::OutOfMemory),
+			Err(_) => Err(Error::AddressInvalid),
+		}
+	}
+
+	/// Returns the scheme of the URL, such as `tcp` or `inproc`.
+	pub fn scheme(&self) -> &str { unsafe { self.field((*self.url.as_ptr()).u_scheme) } }
+
+	/// Returns the name of the host, without the port.
+	///
+	/// This will be empty if the URL does not contain a host.
+	pub fn hostname(&self) -> &str { unsafe { self.field((*self.url.as_ptr()).u_hostname) } }
+
+	/// Returns the port of the URL, if one was specified.
+	///
+	/// For some schemes, such as `http`, NNG fills in the default port when
+	/// one was not specified.
+	pub fn port(&self) -> Option<u16>
+	{
+		unsafe { self.field((*self.url.as_ptr()).u_port) }.parse().ok()
+	}
+
+	/// Returns the path of the URL.
+	pub fn path(&self) -> &str { unsafe { self.field((*self.url.as_ptr()).u_path) } }
+
+	/// Returns the query portion of the URL, if any.
+	pub fn query(&self) -> Option<&str>
+	{
+		unsafe { self.opt_field((*self.url.as_ptr()).u_query) }
+	}
+
+	/// Returns the fragment portion of the URL, if any.
+	pub fn fragment(&self) -> Option<&str>
+	{
+		unsafe { self.opt_field((*self.url.as_ptr()).u_fragment) }
+	}
+
+	/// Returns the user information portion of the URL, if any.
+	pub fn userinfo(&self) -> Option<&str>
+	{
+		unsafe { self.opt_field((*self.url.as_ptr()).u_userinfo) }
+	}
+
+	/// Returns the URL as originally provided to NNG.
+	pub fn as_str(&self) -> &str { unsafe { self.field((*self.url.as_ptr()).u_rawurl) } }
+
+	/// Returns the underlying `nng_url`.
+	pub(crate) const fn as_ptr(&self) -> *mut nng_sys::nng_url { self.url.as_ptr() }
+
+	/// Converts a field of the URL into a string, treating null as empty.
+	///
+	/// This is unsafe because the pointer must be null or point to a string
+	/// owned by this URL.
+	unsafe fn field(&self, ptr: *const c_char) -> &str { self.opt_field(ptr).unwrap_or("") }
+
+	/// Converts a field of the URL into a string, if it is present.
+	///
+	/// This is unsafe because the pointer must be null or point to a string
+	/// owned by this URL.
+	unsafe fn opt_field(&self, ptr: *const c_char) -> Option<&str>
+	{
+		if ptr.is_null() {
+			None
+		}
+		else {
+			// The URL was created from a Rust string and NNG only ever decodes
+			// unreserved (ASCII) characters, so the fields must be valid UTF-8.
+			Some(CStr::from_ptr(ptr).to_str().expect("NNG returned a URL that is not UTF-8"))
+		}
+	}
+}
+
+impl Clone for Url
+{
+	fn clone(&self) -> Self
+	{
+		let mut url: *mut nng_sys::nng_url = ptr::null_mut();
+		let rv = unsafe { nng_sys::nng_url_clone(&mut url as *mut _, self.url.as_ptr()) };
+
+		let url = validate_ptr(rv, url).expect("Failed to clone the NNG URL");
+		Url { url }
+	}
+}
+
+impl FromStr for Url
+{
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self> { Url::parse(s) }
+}
+
+impl fmt::Debug for Url
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		f.debug_tuple("Url").field(&self.as_str()).finish()
+	}
+}
+
+impl fmt::Display for Url
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", self.as_str()) }
+}
+
+impl Drop for Url
+{
+	fn drop(&mut self)
+	{
+		unsafe { nng_sys::nng_url_free(self.url.as_ptr()) }
+	}
+}
+
+// The URL is never modified after it has been parsed and is exclusively owned
+// by this type.
+unsafe impl Send for Url {}
+unsafe impl Sync for Url {}